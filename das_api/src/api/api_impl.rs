@@ -2,27 +2,46 @@ use digital_asset_types::{
     dao::{
         scopes::asset::get_grouping,
         sea_orm_active_enums::{
-            OwnerType, RoyaltyTargetType, SpecificationAssetClass, SpecificationVersions,
+            Instruction, OwnerType, RoyaltyTargetType, SpecificationAssetClass,
+            SpecificationVersions,
         },
         Cursor, PageOptions, SearchAssetsQuery,
     },
     dapi::{
-        get_asset, get_asset_proofs, get_asset_signatures, get_assets, get_assets_by_authority,
-        get_assets_by_creator, get_assets_by_group, get_assets_by_owner, get_proof_for_asset,
-        search_assets,
+        add_tree_info_to_proofs, get_asset, get_asset_history, get_asset_proofs,
+        get_asset_signatures, get_assets, get_assets_by_authority, get_assets_by_creator,
+        get_assets_by_delegate, get_assets_by_group, get_assets_by_owner, get_assets_by_tree,
+        get_collection_holders, get_collection_stats, get_context, get_historical_asset_proofs,
+        get_historical_proof_for_asset, get_indexer_health, get_indexer_status,
+        get_owner_collections, get_proof_for_asset, get_token_accounts, get_tree_info,
+        search_assets, HealthLimits, ProofVersion,
     },
     rpc::{
         filter::{AssetSortBy, SearchConditionType},
-        response::GetGroupingResponse,
+        response::{
+            AssetHistory, CollectionHolderList, CollectionStats, GetGroupingResponse,
+            IndexerHealth, IndexerStatus, OwnerCollections, RpcContext, TokenAccountList, TreeInfo,
+        },
     },
     rpc::{OwnershipModel, RoyaltyModel},
 };
 use open_rpc_derive::document_rpc;
-use sea_orm::{sea_query::ConditionType, ConnectionTrait, DbBackend, Statement};
+use sea_orm::sea_query::ConditionType;
 
+use super::{
+    asset_updates::{
+        listen_for_asset_updates, AssetUpdates, Subscription, SubscriptionKey, UpdatedAsset,
+    },
+    database::Databases,
+};
 use crate::error::DasApiError;
-use crate::validation::{validate_opt_pubkey, validate_search_with_name};
+use crate::validation::{
+    validate_addresses, validate_cursor_key, validate_opt_pubkey, validate_search_filter,
+    INT_CURSOR_LEN, SLOT_AND_ID_CURSOR_LEN,
+};
 use open_rpc_schema::document::OpenrpcDocument;
+use std::sync::Arc;
+use tokio::sync::broadcast;
 use {
     crate::api::*,
     crate::config::Config,
@@ -30,41 +49,89 @@ use {
     async_trait::async_trait,
     digital_asset_types::rpc::{response::AssetList, Asset, AssetProof},
     sea_orm::{DatabaseConnection, DbErr, SqlxPostgresConnector},
-    sqlx::postgres::PgPoolOptions,
+    sqlx::postgres::PgPool,
 };
 
 pub struct DasApi {
+    /// The database reads go to, the primary unless the api was handed out by
+    /// [ApiContract::reader].
     db_connection: DatabaseConnection,
+    databases: Arc<Databases>,
+    asset_updates: AssetUpdates,
+    health_limits: HealthLimits,
 }
 
 impl DasApi {
     pub async fn from_config(config: Config) -> Result<Self, DasApiError> {
-        let pool = PgPoolOptions::new()
-            .max_connections(250)
-            .connect(&config.database_url)
-            .await?;
+        let databases = Databases::connect(&config).await?;
+        let primary = databases.primary_pool();
 
-        let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        // Replicas don't see the notifications sent on the primary.
+        let asset_updates = listen_for_asset_updates(
+            primary,
+            SqlxPostgresConnector::from_sqlx_postgres_pool(primary.clone()),
+        )
+        .await?;
         Ok(DasApi {
-            db_connection: conn,
+            db_connection: SqlxPostgresConnector::from_sqlx_postgres_pool(primary.clone()),
+            databases,
+            asset_updates,
+            health_limits: HealthLimits {
+                max_lag_slots: config.max_indexer_lag_slots,
+                max_lag_seconds: config.max_indexer_lag_seconds,
+                max_backlog: config.max_indexer_backlog,
+            },
         })
     }
 
-    fn get_cursor(&self, cursor: &Option<String>) -> Result<Cursor, DasApiError> {
+    fn reading_from(&self, pool: &PgPool) -> Self {
+        DasApi {
+            db_connection: SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone()),
+            databases: Arc::clone(&self.databases),
+            asset_updates: self.asset_updates.clone(),
+            health_limits: self.health_limits,
+        }
+    }
+
+    fn get_cursor(
+        &self,
+        cursor: &Option<String>,
+        sort_by: AssetSortBy,
+    ) -> Result<Cursor, DasApiError> {
         match cursor {
             Some(cursor_b64) => {
-                let cursor_vec = bs58::decode(cursor_b64)
-                    .into_vec()
-                    .map_err(|_| DasApiError::CursorValidationError(cursor_b64.clone()))?;
-                let cursor_struct = Cursor {
-                    id: Some(cursor_vec),
-                };
+                let cursor_struct = Cursor::decode(cursor_b64)
+                    .ok_or_else(|| DasApiError::CursorValidationError(cursor_b64.clone()))?;
+                // Cursors carry the sort column value unless the results are ordered by id, so a
+                // cursor is only valid for the sorting it was issued with.
+                let expects_sort_value = !matches!(sort_by, AssetSortBy::Id | AssetSortBy::None);
+                if cursor_struct.sort_value.is_some() != expects_sort_value {
+                    return Err(DasApiError::CursorValidationError(cursor_b64.clone()));
+                }
                 Ok(cursor_struct)
             }
             None => Ok(Cursor::default()),
         }
     }
 
+    fn get_proof_version(
+        &self,
+        seq: Option<u64>,
+        root: &Option<String>,
+    ) -> Result<Option<ProofVersion>, DasApiError> {
+        match (seq, root) {
+            (None, None) => Ok(None),
+            (Some(seq), None) => Ok(Some(ProofVersion::Seq(seq as i64))),
+            (None, Some(root)) => {
+                let root = validate_pubkey(root.clone())?;
+                Ok(Some(ProofVersion::Root(root.to_bytes().to_vec())))
+            }
+            (Some(_), Some(_)) => Err(DasApiError::ValidationError(
+                "Only one of seq and root can be provided".to_string(),
+            )),
+        }
+    }
+
     fn validate_pagination(
         &self,
         limit: Option<u32>,
@@ -126,10 +193,7 @@ impl DasApi {
         page_opt.limit = limit.map(|x| x as u64).unwrap_or(1000);
         if is_cursor_enabled {
             if let Some(sort) = &sorting {
-                if sort.sort_by != AssetSortBy::Id {
-                    return Err(DasApiError::PaginationSortingValidationError);
-                }
-                page_opt.cursor = Some(self.get_cursor(cursor)?);
+                page_opt.cursor = Some(self.get_cursor(cursor, sort.sort_by)?);
             }
         } else {
             page_opt.page = page.map(|x| x as u64);
@@ -151,14 +215,52 @@ pub fn not_found(asset_id: &String) -> DbErr {
 #[document_rpc]
 #[async_trait]
 impl ApiContract for DasApi {
-    async fn check_health(self: &DasApi) -> Result<(), DasApiError> {
-        self.db_connection
-            .execute(Statement::from_string(
-                DbBackend::Postgres,
-                "SELECT 1".to_string(),
-            ))
-            .await?;
-        Ok(())
+    async fn check_health(self: &DasApi) -> Result<IndexerHealth, DasApiError> {
+        let mut health = get_indexer_health(self.databases.primary(), self.health_limits).await?;
+        health.replicas = self.databases.replica_statuses();
+        if !health.healthy {
+            let reason = health
+                .streams
+                .iter()
+                .find_map(|s| self.health_limits.violation(s))
+                .unwrap_or_else(|| "no stream has made progress".to_string());
+            return Err(DasApiError::UnhealthyIndexerError(reason));
+        }
+        Ok(health)
+    }
+
+    async fn reader(
+        self: &DasApi,
+        min_context_slot: Option<u64>,
+    ) -> Result<(RpcContext, Box<dyn ApiContract>), DasApiError> {
+        let replica = self.databases.replica(min_context_slot);
+        let Some(min_context_slot) = min_context_slot else {
+            // Without a slot to reach, the slots seen by the last health check are good enough.
+            let (pool, slot) =
+                replica.unwrap_or((self.databases.primary_pool(), self.databases.primary_slot()));
+            return Ok((RpcContext { slot }, Box::new(self.reading_from(pool))));
+        };
+        if let Some((replica, _)) = replica {
+            let api = self.reading_from(replica);
+            let context = get_context(&api.db_connection).await?;
+            // Replicas are only checked periodically, so one may still be behind the request.
+            if context.slot >= min_context_slot {
+                return Ok((context, Box::new(api)));
+            }
+        }
+        let api = self.reading_from(self.databases.primary_pool());
+        let context = get_context(&api.db_connection).await?;
+        if context.slot < min_context_slot {
+            return Err(DasApiError::MinContextSlotNotReached(context.slot));
+        }
+        Ok((context, Box::new(api)))
+    }
+
+    fn subscribe_asset_updates(
+        &self,
+        key: SubscriptionKey,
+    ) -> (broadcast::Receiver<Arc<UpdatedAsset>>, Subscription) {
+        self.asset_updates.subscribe(key)
     }
 
     async fn get_asset_proof(
@@ -167,22 +269,32 @@ impl ApiContract for DasApi {
     ) -> Result<AssetProof, DasApiError> {
         let id = validate_pubkey(payload.id.clone())?;
         let id_bytes = id.to_bytes().to_vec();
-        get_proof_for_asset(&self.db_connection, id_bytes)
-            .await
-            .and_then(|p| {
-                if p.proof.is_empty() {
-                    return Err(not_found(&payload.id));
-                }
-                Ok(p)
-            })
-            .map_err(Into::into)
+        let proof = match self.get_proof_version(payload.seq, &payload.root)? {
+            Some(version) => {
+                get_historical_proof_for_asset(&self.db_connection, id_bytes, &version).await
+            }
+            None => get_proof_for_asset(&self.db_connection, id_bytes).await,
+        };
+        let mut proof = proof.and_then(|p| {
+            if p.proof.is_empty() {
+                return Err(not_found(&payload.id));
+            }
+            Ok(p)
+        })?;
+        add_tree_info_to_proofs(&self.db_connection, [&mut proof], payload.truncate_canopy).await?;
+        Ok(proof)
     }
 
     async fn get_asset_proofs(
         self: &DasApi,
         payload: GetAssetProofs,
     ) -> Result<HashMap<String, Option<AssetProof>>, DasApiError> {
-        let GetAssetProofs { ids } = payload;
+        let GetAssetProofs {
+            ids,
+            seq,
+            root,
+            truncate_canopy,
+        } = payload;
 
         let batch_size = ids.len();
         if batch_size > 1000 {
@@ -194,7 +306,13 @@ impl ApiContract for DasApi {
             .map(|id| validate_pubkey(id.clone()).map(|id| id.to_bytes().to_vec()))
             .collect::<Result<Vec<Vec<u8>>, _>>()?;
 
-        let proofs = get_asset_proofs(&self.db_connection, id_bytes).await?;
+        let mut proofs = match self.get_proof_version(seq, &root)? {
+            Some(version) => {
+                get_historical_asset_proofs(&self.db_connection, id_bytes, &version).await?
+            }
+            None => get_asset_proofs(&self.db_connection, id_bytes).await?,
+        };
+        add_tree_info_to_proofs(&self.db_connection, proofs.values_mut(), truncate_canopy).await?;
 
         let result: HashMap<String, Option<AssetProof>> = ids
             .iter()
@@ -242,6 +360,7 @@ impl ApiContract for DasApi {
     ) -> Result<AssetList, DasApiError> {
         let GetAssetsByOwner {
             owner_address,
+            owner_addresses,
             sort_by,
             limit,
             page,
@@ -252,15 +371,57 @@ impl ApiContract for DasApi {
         } = payload;
         let before: Option<String> = before.filter(|before| !before.is_empty());
         let after: Option<String> = after.filter(|after| !after.is_empty());
-        let owner_address = validate_pubkey(owner_address.clone())?;
-        let owner_address_bytes = owner_address.to_bytes().to_vec();
+        let owners = validate_addresses(
+            &owner_address,
+            &owner_addresses,
+            "ownerAddress",
+            "ownerAddresses",
+        )?
+        .ok_or_else(|| {
+            DasApiError::ValidationError(
+                "Must provide either 'ownerAddress' or 'ownerAddresses'".to_string(),
+            )
+        })?;
         let sort_by = sort_by.unwrap_or_default();
         let options = options.unwrap_or_default();
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
         get_assets_by_owner(
             &self.db_connection,
-            owner_address_bytes,
+            owners,
+            sort_by,
+            &page_options,
+            &options,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_assets_by_delegate(
+        self: &DasApi,
+        payload: GetAssetsByDelegate,
+    ) -> Result<AssetList, DasApiError> {
+        let GetAssetsByDelegate {
+            delegate_address,
+            sort_by,
+            limit,
+            page,
+            before,
+            after,
+            options,
+            cursor,
+        } = payload;
+        let before: Option<String> = before.filter(|before| !before.is_empty());
+        let after: Option<String> = after.filter(|after| !after.is_empty());
+        let delegate_address = validate_pubkey(delegate_address.clone())?;
+        let delegate_address_bytes = delegate_address.to_bytes().to_vec();
+        let sort_by = sort_by.unwrap_or_default();
+        let options = options.unwrap_or_default();
+        let page_options =
+            self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
+        get_assets_by_delegate(
+            &self.db_connection,
+            delegate_address_bytes,
             sort_by,
             &page_options,
             &options,
@@ -308,6 +469,7 @@ impl ApiContract for DasApi {
     ) -> Result<AssetList, DasApiError> {
         let GetAssetsByCreator {
             creator_address,
+            creator_addresses,
             only_verified,
             sort_by,
             limit,
@@ -317,8 +479,17 @@ impl ApiContract for DasApi {
             options,
             cursor,
         } = payload;
-        let creator_address = validate_pubkey(creator_address.clone())?;
-        let creator_address_bytes = creator_address.to_bytes().to_vec();
+        let creators = validate_addresses(
+            &creator_address,
+            &creator_addresses,
+            "creatorAddress",
+            "creatorAddresses",
+        )?
+        .ok_or_else(|| {
+            DasApiError::ValidationError(
+                "Must provide either 'creatorAddress' or 'creatorAddresses'".to_string(),
+            )
+        })?;
 
         let sort_by = sort_by.unwrap_or_default();
         let page_options =
@@ -327,7 +498,7 @@ impl ApiContract for DasApi {
         let options = options.unwrap_or_default();
         get_assets_by_creator(
             &self.db_connection,
-            creator_address_bytes,
+            creators,
             only_verified,
             sort_by,
             &page_options,
@@ -369,17 +540,64 @@ impl ApiContract for DasApi {
         .map_err(Into::into)
     }
 
+    async fn get_assets_by_tree(
+        self: &DasApi,
+        payload: GetAssetsByTree,
+    ) -> Result<AssetList, DasApiError> {
+        let GetAssetsByTree {
+            tree,
+            from_leaf,
+            to_leaf,
+            limit,
+            page,
+            options,
+            cursor,
+        } = payload;
+        let tree = validate_pubkey(tree)?;
+        if let (Some(from_leaf), Some(to_leaf)) = (from_leaf, to_leaf) {
+            if from_leaf > to_leaf {
+                return Err(DasApiError::ValidationError(
+                    "'fromLeaf' must not be greater than 'toLeaf'".to_string(),
+                ));
+            }
+        }
+        let options = options.unwrap_or_default();
+        let page_options = self.validate_pagination(
+            limit,
+            page,
+            &None,
+            &None,
+            &cursor,
+            Some(AssetSorting::default()),
+        )?;
+        validate_cursor_key(&page_options.cursor, &[INT_CURSOR_LEN])?;
+        get_assets_by_tree(
+            &self.db_connection,
+            tree.to_bytes().to_vec(),
+            from_leaf.map(|leaf| leaf as i64),
+            to_leaf.map(|leaf| leaf as i64),
+            &page_options,
+            &options,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
     async fn search_assets(&self, payload: SearchAssets) -> Result<AssetList, DasApiError> {
         let SearchAssets {
             negate,
             condition_type,
             interface,
             owner_address,
+            owner_addresses,
             owner_type,
             creator_address,
+            creator_addresses,
             creator_verified,
             authority_address,
+            authority_addresses,
             grouping,
+            groupings,
             delegate,
             frozen,
             supply,
@@ -399,6 +617,11 @@ impl ApiContract for DasApi {
             options,
             cursor,
             name,
+            symbol,
+            text_match_mode,
+            case_insensitive,
+            attributes,
+            filter,
         } = payload;
 
         // Deserialize search assets query
@@ -406,16 +629,64 @@ impl ApiContract for DasApi {
             interface.map(|x| x.into());
         let specification_version = spec.clone().map(|x| x.0);
         let specification_asset_class = spec.map(|x| x.1);
-        let condition_type = condition_type.map(|x| match x {
+        let to_condition_type = |x| match x {
             SearchConditionType::Any => ConditionType::Any,
             SearchConditionType::All => ConditionType::All,
-        });
-        let owner_address = validate_opt_pubkey(&owner_address)?;
-        let name = validate_search_with_name(&name, &owner_address)?;
-        let creator_address = validate_opt_pubkey(&creator_address)?;
+        };
+        let condition_type = condition_type.map(to_condition_type);
+        let attributes_condition_type = attributes
+            .as_ref()
+            .and_then(|a| a.condition_type)
+            .map(to_condition_type);
+        let attributes = match attributes {
+            Some(a) if a.traits.is_empty() => {
+                return Err(DasApiError::ValidationError(
+                    "Attribute filter must contain at least one trait".to_string(),
+                ));
+            }
+            a => a.map(|a| {
+                a.traits
+                    .into_iter()
+                    .map(|t| (t.trait_type, t.value))
+                    .collect()
+            }),
+        };
+        let owner_addresses = validate_addresses(
+            &owner_address,
+            &owner_addresses,
+            "ownerAddress",
+            "ownerAddresses",
+        )?;
+        let creator_addresses = validate_addresses(
+            &creator_address,
+            &creator_addresses,
+            "creatorAddress",
+            "creatorAddresses",
+        )?;
         let delegate = validate_opt_pubkey(&delegate)?;
 
-        let authority_address = validate_opt_pubkey(&authority_address)?;
+        let authority_addresses = validate_addresses(
+            &authority_address,
+            &authority_addresses,
+            "authorityAddress",
+            "authorityAddresses",
+        )?;
+        let groupings = match (grouping, groupings) {
+            (Some(_), Some(_)) => {
+                return Err(DasApiError::ValidationError(
+                    "Cannot provide both 'grouping' and 'groupings'".to_string(),
+                ));
+            }
+            (_, Some(groupings)) if groupings.is_empty() => {
+                return Err(DasApiError::ValidationError(
+                    "'groupings' must not be empty".to_string(),
+                ));
+            }
+            (_, Some(groupings)) if groupings.len() > 1000 => {
+                return Err(DasApiError::BatchSizeExceededError);
+            }
+            (grouping, groupings) => groupings.or_else(|| grouping.map(|g| vec![g])),
+        };
         let supply_mint = validate_opt_pubkey(&supply_mint)?;
         let royalty_target = validate_opt_pubkey(&royalty_target)?;
 
@@ -423,6 +694,7 @@ impl ApiContract for DasApi {
             OwnershipModel::Single => OwnerType::Single,
             OwnershipModel::Token => OwnerType::Token,
         });
+        let options = options.unwrap_or_default();
         let royalty_target_type = royalty_target_type.map(|x| match x {
             RoyaltyModel::Creators => RoyaltyTargetType::Creators,
             RoyaltyModel::Fanout => RoyaltyTargetType::Fanout,
@@ -433,12 +705,12 @@ impl ApiContract for DasApi {
             condition_type,
             specification_version,
             specification_asset_class,
-            owner_address,
+            owner_addresses,
             owner_type,
-            creator_address,
+            creator_addresses,
             creator_verified,
-            authority_address,
-            grouping,
+            authority_addresses,
+            groupings,
             delegate,
             frozen,
             supply,
@@ -451,8 +723,14 @@ impl ApiContract for DasApi {
             burnt,
             json_uri,
             name,
+            symbol,
+            text_match_mode,
+            case_insensitive,
+            attributes,
+            attributes_condition_type,
+            show_fungible: options.show_fungible,
+            filter: filter.map(validate_search_filter).transpose()?,
         };
-        let options = options.unwrap_or_default();
         let sort_by = sort_by.unwrap_or_default();
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
@@ -476,6 +754,7 @@ impl ApiContract for DasApi {
             leaf_index,
             cursor,
             sort_direction,
+            instruction,
         } = payload;
 
         if !((id.is_some() && tree.is_none() && leaf_index.is_none())
@@ -487,20 +766,94 @@ impl ApiContract for DasApi {
         }
         let id = validate_opt_pubkey(&id)?;
         let tree = validate_opt_pubkey(&tree)?;
+        let instruction = instruction
+            .map(|ix| match Instruction::from(ix.as_str()) {
+                Instruction::Unknown if ix != "Unknown" => Err(DasApiError::ValidationError(
+                    format!("Unknown instruction: {}", ix),
+                )),
+                instruction => Ok(instruction),
+            })
+            .transpose()?;
 
-        let page_options = self.validate_pagination(limit, page, &before, &after, &cursor, None)?;
+        // Signatures are ordered by sequence number, or by slot and row id for uncompressed
+        // assets, neither of which fits in a pubkey bound.
+        if before.is_some() || after.is_some() {
+            return Err(DasApiError::PaginationKeysetUnsupportedError);
+        }
+        let page_options = self.validate_pagination(
+            limit,
+            page,
+            &before,
+            &after,
+            &cursor,
+            Some(AssetSorting::default()),
+        )?;
+        // Compressed assets are paged by sequence number, uncompressed ones by slot and row id,
+        // and which one an id refers to is only known once the asset is loaded.
+        let cursor_lengths: &[usize] = if tree.is_some() {
+            &[INT_CURSOR_LEN]
+        } else {
+            &[INT_CURSOR_LEN, SLOT_AND_ID_CURSOR_LEN]
+        };
+        validate_cursor_key(&page_options.cursor, cursor_lengths)?;
 
         get_asset_signatures(
             &self.db_connection,
             id,
             tree,
             leaf_index,
+            instruction,
             page_options,
             sort_direction,
         )
         .await
         .map_err(Into::into)
     }
+    async fn get_asset_history(
+        self: &DasApi,
+        payload: GetAssetHistory,
+    ) -> Result<AssetHistory, DasApiError> {
+        let GetAssetHistory {
+            id,
+            limit,
+            page,
+            cursor,
+            sort_direction,
+        } = payload;
+
+        let id = validate_pubkey(id)?;
+        let page_options = self.validate_pagination(
+            limit,
+            page,
+            &None,
+            &None,
+            &cursor,
+            Some(AssetSorting::default()),
+        )?;
+        validate_cursor_key(&page_options.cursor, &[INT_CURSOR_LEN])?;
+
+        get_asset_history(
+            &self.db_connection,
+            id.to_bytes().to_vec(),
+            page_options,
+            sort_direction,
+        )
+        .await
+        .map_err(Into::into)
+    }
+    async fn get_tree_info(self: &DasApi, payload: GetTreeInfo) -> Result<TreeInfo, DasApiError> {
+        let id = validate_pubkey(payload.id)?;
+        get_tree_info(&self.db_connection, id.to_bytes().to_vec())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_indexer_status(self: &DasApi) -> Result<IndexerStatus, DasApiError> {
+        let mut status = get_indexer_status(self.databases.primary(), self.health_limits).await?;
+        status.health.replicas = self.databases.replica_statuses();
+        Ok(status)
+    }
+
     async fn get_grouping(
         self: &DasApi,
         payload: GetGrouping,
@@ -512,8 +865,109 @@ impl ApiContract for DasApi {
         let gs = get_grouping(&self.db_connection, group_key.clone(), group_value.clone()).await?;
         Ok(GetGroupingResponse {
             group_key,
-            group_name: group_value,
+            group_name: gs.name.unwrap_or(group_value),
             group_size: gs.size,
         })
     }
+
+    async fn get_collection_stats(
+        self: &DasApi,
+        payload: GetCollectionStats,
+    ) -> Result<CollectionStats, DasApiError> {
+        let collection = validate_pubkey(payload.group_value)?;
+        get_collection_stats(&self.db_connection, collection.to_bytes().to_vec())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_owner_collections(
+        self: &DasApi,
+        payload: GetOwnerCollections,
+    ) -> Result<OwnerCollections, DasApiError> {
+        let GetOwnerCollections {
+            owner_address,
+            limit,
+            page,
+        } = payload;
+        let owner = validate_pubkey(owner_address)?;
+        // Collections are ordered by how many assets are held, so only pages are supported.
+        let page_options = self.validate_pagination(limit, page, &None, &None, &None, None)?;
+        get_owner_collections(
+            &self.db_connection,
+            owner.to_bytes().to_vec(),
+            &page_options,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_collection_holders(
+        self: &DasApi,
+        payload: GetCollectionHolders,
+    ) -> Result<CollectionHolderList, DasApiError> {
+        let GetCollectionHolders {
+            group_value,
+            limit,
+            page,
+            before,
+            after,
+            cursor,
+        } = payload;
+        validate_pubkey(group_value.clone())?;
+        let page_options = self.validate_pagination(
+            limit,
+            page,
+            &before,
+            &after,
+            &cursor,
+            Some(AssetSorting::default()),
+        )?;
+        get_collection_holders(&self.db_connection, group_value, &page_options)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_token_accounts(
+        self: &DasApi,
+        payload: GetTokenAccounts,
+    ) -> Result<TokenAccountList, DasApiError> {
+        let GetTokenAccounts {
+            owner_address,
+            mint_address,
+            limit,
+            page,
+            before,
+            after,
+            cursor,
+        } = payload;
+
+        if owner_address.is_none() && mint_address.is_none() {
+            return Err(DasApiError::ValidationError(
+                "Must provide either 'ownerAddress' or 'mintAddress'".to_string(),
+            ));
+        }
+        let owner_address = validate_opt_pubkey(&owner_address)?;
+        let mint_address = validate_opt_pubkey(&mint_address)?;
+        let before: Option<String> = before.filter(|before| !before.is_empty());
+        let after: Option<String> = after.filter(|after| !after.is_empty());
+
+        // Token accounts are always ordered by their address, so they page the same way
+        // assets sorted by id do.
+        let page_options = self.validate_pagination(
+            limit,
+            page,
+            &before,
+            &after,
+            &cursor,
+            Some(AssetSorting::default()),
+        )?;
+        get_token_accounts(
+            &self.db_connection,
+            owner_address,
+            mint_address,
+            &page_options,
+        )
+        .await
+        .map_err(Into::into)
+    }
 }
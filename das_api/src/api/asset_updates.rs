@@ -0,0 +1,225 @@
+use crate::error::DasApiError;
+use digital_asset_types::{
+    dao::{get_asset_update_keys, AssetUpdate, AssetUpdateKeys, ASSET_UPDATES_CHANNEL},
+    dapi::get_asset_including_burnt,
+    rpc::{options::Options, Asset},
+};
+use futures::{stream, StreamExt};
+use log::{error, warn};
+use sea_orm::{DatabaseConnection, DbErr};
+use sqlx::postgres::{PgListener, PgPool};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{broadcast, mpsc};
+
+/// Number of asset updates buffered per subscriber before the slowest ones start skipping.
+const ASSET_UPDATES_CAPACITY: usize = 1024;
+/// Most notifications matched against the subscriptions with a single query.
+const ASSET_UPDATES_BATCH_SIZE: usize = 100;
+/// Most updated assets loaded at once.
+const ASSET_LOADS_CONCURRENCY: usize = 10;
+
+/// An asset as it was loaded after an update.
+#[derive(Debug, Clone)]
+pub struct UpdatedAsset {
+    pub asset: Asset,
+    /// The owner the asset was transferred away from by the update.
+    pub previous_owner: Option<String>,
+}
+
+/// What a subscription listens for, checked against each update before its asset is loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubscriptionKey {
+    Id(Vec<u8>),
+    /// Matches both the current and the previous owner of an asset.
+    Owner(Vec<u8>),
+    Group {
+        key: String,
+        value: String,
+    },
+}
+
+/// Number of live subscriptions per key.
+#[derive(Debug, Default)]
+struct Subscriptions(Mutex<HashMap<SubscriptionKey, usize>>);
+
+impl Subscriptions {
+    fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    fn matches(&self, update: &AssetUpdate, keys: &[AssetUpdateKeys]) -> bool {
+        let subscriptions = self.0.lock().unwrap();
+        let subscribed = |key: SubscriptionKey| subscriptions.contains_key(&key);
+        subscribed(SubscriptionKey::Id(update.id.clone()))
+            || update
+                .previous_owner
+                .iter()
+                .chain(keys.iter().filter_map(|keys| keys.owner.as_ref()))
+                .any(|owner| subscribed(SubscriptionKey::Owner(owner.clone())))
+            || keys
+                .iter()
+                .any(|keys| match (&keys.group_key, &keys.group_value) {
+                    (Some(key), Some(value)) => subscribed(SubscriptionKey::Group {
+                        key: key.clone(),
+                        value: value.clone(),
+                    }),
+                    _ => false,
+                })
+    }
+}
+
+/// Keeps the key of a subscription registered until it's dropped.
+#[derive(Debug)]
+pub struct Subscription {
+    key: SubscriptionKey,
+    subscriptions: Arc<Subscriptions>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut subscriptions = self.subscriptions.0.lock().unwrap();
+        if let Some(count) = subscriptions.get_mut(&self.key) {
+            *count -= 1;
+            if *count == 0 {
+                subscriptions.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// The assets written by the ingester, broadcast to the subscriptions they match.
+#[derive(Debug, Clone)]
+pub struct AssetUpdates {
+    sender: broadcast::Sender<Arc<UpdatedAsset>>,
+    subscriptions: Arc<Subscriptions>,
+}
+
+impl AssetUpdates {
+    /// Registers a subscription to `key`. The receiver gets every update matching any live
+    /// subscription, so subscribers still filter them.
+    pub fn subscribe(
+        &self,
+        key: SubscriptionKey,
+    ) -> (broadcast::Receiver<Arc<UpdatedAsset>>, Subscription) {
+        *self
+            .subscriptions
+            .0
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default() += 1;
+        let subscription = Subscription {
+            key,
+            subscriptions: Arc::clone(&self.subscriptions),
+        };
+        (self.sender.subscribe(), subscription)
+    }
+}
+
+/// Listens for the asset ids announced by the ingester and broadcasts the freshly loaded assets
+/// to the subscriptions.  Only the assets matching a subscription are loaded.
+pub async fn listen_for_asset_updates(
+    pool: &PgPool,
+    db: DatabaseConnection,
+) -> Result<AssetUpdates, DasApiError> {
+    let (sender, _) = broadcast::channel(ASSET_UPDATES_CAPACITY);
+    let subscriptions = Arc::new(Subscriptions::default());
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(ASSET_UPDATES_CHANNEL).await?;
+
+    // Notifications are received apart from loading the assets, so the ones arriving meanwhile
+    // can be handled together.
+    let (notifications, mut received) = mpsc::unbounded_channel();
+    let listened = Arc::clone(&subscriptions);
+    tokio::spawn(async move {
+        loop {
+            // The listener reconnects on the next call after a connection error, dropping the
+            // notifications sent in between.
+            let notification = match listener.recv().await {
+                Ok(notification) => notification,
+                Err(e) => {
+                    error!("Failed to receive asset update: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+            if listened.is_empty() {
+                continue;
+            }
+            match AssetUpdate::parse(notification.payload()) {
+                Ok(update) => {
+                    if notifications.send(update).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => warn!("Invalid asset update {}: {}", notification.payload(), e),
+            }
+        }
+    });
+
+    let updates = AssetUpdates {
+        sender: sender.clone(),
+        subscriptions: Arc::clone(&subscriptions),
+    };
+    tokio::spawn(async move {
+        while let Some(update) = received.recv().await {
+            let mut batch = vec![update];
+            while batch.len() < ASSET_UPDATES_BATCH_SIZE {
+                match received.try_recv() {
+                    Ok(update) => batch.push(update),
+                    Err(_) => break,
+                }
+            }
+            if let Err(e) = broadcast_updates(&db, &sender, &subscriptions, batch).await {
+                error!("Failed to load updated assets: {}", e);
+            }
+        }
+    });
+
+    Ok(updates)
+}
+
+async fn broadcast_updates(
+    db: &DatabaseConnection,
+    sender: &broadcast::Sender<Arc<UpdatedAsset>>,
+    subscriptions: &Subscriptions,
+    mut batch: Vec<AssetUpdate>,
+) -> Result<(), DbErr> {
+    batch.dedup();
+    let ids = batch.iter().map(|update| update.id.clone()).collect();
+    let mut keys: HashMap<Vec<u8>, Vec<AssetUpdateKeys>> = HashMap::new();
+    for asset_keys in get_asset_update_keys(db, ids).await? {
+        keys.entry(asset_keys.id.clone())
+            .or_default()
+            .push(asset_keys);
+    }
+    let matching = batch.into_iter().filter(|update| {
+        subscriptions.matches(update, keys.get(&update.id).map_or(&[], Vec::as_slice))
+    });
+
+    let mut loaded = stream::iter(matching)
+        .map(|AssetUpdate { id, previous_owner }| async move {
+            // Burning leaves the asset without supply, and subscribers are told about that too.
+            let asset = get_asset_including_burnt(db, id, &Options::default()).await;
+            (asset, previous_owner)
+        })
+        .buffered(ASSET_LOADS_CONCURRENCY);
+    while let Some((asset, previous_owner)) = loaded.next().await {
+        match asset {
+            // All receivers may have gone away since the notification was received.
+            Ok(asset) => drop(sender.send(Arc::new(UpdatedAsset {
+                asset,
+                previous_owner: previous_owner.map(|owner| bs58::encode(owner).into_string()),
+            }))),
+            // Accounts can be indexed before the rest of the asset, e.g. a token account
+            // ahead of its metadata.
+            Err(DbErr::RecordNotFound(_)) => {}
+            Err(e) => error!("Failed to load updated asset: {}", e),
+        }
+    }
+    Ok(())
+}
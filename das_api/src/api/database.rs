@@ -0,0 +1,189 @@
+use crate::{config::Config, error::DasApiError};
+use digital_asset_types::{dapi::get_context, rpc::response::ReplicaStatus};
+use log::warn;
+use sea_orm::{DatabaseConnection, SqlxPostgresConnector};
+use sqlx::postgres::{PgConnectOptions, PgPool, PgPoolOptions};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    time::Duration,
+};
+
+const DEFAULT_MAX_CONNECTIONS: u32 = 250;
+/// About a minute of slots.
+const DEFAULT_MAX_REPLICA_LAG_SLOTS: u64 = 150;
+const REPLICA_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const REPLICA_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The primary database and its read replicas.
+pub struct Databases {
+    primary_pool: PgPool,
+    primary: DatabaseConnection,
+    replicas: Vec<Replica>,
+    next_replica: AtomicUsize,
+    /// Context slot of the primary at the last health check.
+    primary_slot: AtomicU64,
+    max_replica_lag_slots: u64,
+}
+
+struct Replica {
+    pool: PgPool,
+    conn: DatabaseConnection,
+    /// Whether the last health check reached the replica.
+    reachable: AtomicBool,
+    /// Context slot of the replica at the last health check.
+    slot: AtomicU64,
+}
+
+impl Databases {
+    /// Connects to the primary and the replicas. Replicas connect lazily so one being down doesn't
+    /// keep the api from starting, and only serve reads once they have passed a health check.
+    pub async fn connect(config: &Config) -> Result<Arc<Self>, DasApiError> {
+        let pool = pool_options(config)
+            .connect_with(connect_options(&config.database_url, config)?)
+            .await?;
+        let mut replicas = vec![];
+        for url in &config.replica_database_urls {
+            let pool = pool_options(config).connect_lazy_with(connect_options(url, config)?);
+            replicas.push(Replica {
+                conn: SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone()),
+                pool,
+                reachable: AtomicBool::new(false),
+                slot: AtomicU64::new(0),
+            });
+        }
+
+        let databases = Arc::new(Databases {
+            primary: SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone()),
+            primary_pool: pool,
+            replicas,
+            next_replica: AtomicUsize::new(0),
+            primary_slot: AtomicU64::new(0),
+            max_replica_lag_slots: config
+                .max_replica_lag_slots
+                .unwrap_or(DEFAULT_MAX_REPLICA_LAG_SLOTS),
+        });
+        databases.check_slots().await;
+        spawn_slot_checks(Arc::downgrade(&databases));
+        Ok(databases)
+    }
+
+    pub const fn primary(&self) -> &DatabaseConnection {
+        &self.primary
+    }
+
+    pub const fn primary_pool(&self) -> &PgPool {
+        &self.primary_pool
+    }
+
+    /// Context slot of the primary at the last health check.
+    pub fn primary_slot(&self) -> u64 {
+        self.primary_slot.load(Ordering::Relaxed)
+    }
+
+    /// The next replica in turn that is reachable, keeps up with the primary and has reached
+    /// `min_context_slot`, along with its context slot at the last health check. `None` when reads
+    /// should go to the primary.
+    pub fn replica(&self, min_context_slot: Option<u64>) -> Option<(&PgPool, u64)> {
+        if self.replicas.is_empty() {
+            return None;
+        }
+        let start = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        (0..self.replicas.len())
+            .map(|i| &self.replicas[(start + i) % self.replicas.len()])
+            .find(|replica| {
+                self.serves_reads(replica)
+                    && min_context_slot
+                        .map_or(true, |min| replica.slot.load(Ordering::Relaxed) >= min)
+            })
+            .map(|replica| (&replica.pool, replica.slot.load(Ordering::Relaxed)))
+    }
+
+    /// The state of each replica as of the last health check, in configuration order.
+    pub fn replica_statuses(&self) -> Vec<ReplicaStatus> {
+        let primary_slot = self.primary_slot.load(Ordering::Relaxed);
+        self.replicas
+            .iter()
+            .map(|replica| {
+                let slot = replica.slot.load(Ordering::Relaxed);
+                ReplicaStatus {
+                    reachable: replica.reachable.load(Ordering::Relaxed),
+                    slot,
+                    lag_slots: primary_slot.saturating_sub(slot),
+                    serving_reads: self.serves_reads(replica),
+                }
+            })
+            .collect()
+    }
+
+    fn serves_reads(&self, replica: &Replica) -> bool {
+        let primary_slot = self.primary_slot.load(Ordering::Relaxed);
+        replica.reachable.load(Ordering::Relaxed)
+            && primary_slot.saturating_sub(replica.slot.load(Ordering::Relaxed))
+                <= self.max_replica_lag_slots
+    }
+
+    async fn check_slots(&self) {
+        match get_context(&self.primary).await {
+            Ok(context) => self.primary_slot.store(context.slot, Ordering::Relaxed),
+            Err(e) => warn!("Failed to read the context of the primary database: {}", e),
+        }
+        for (i, replica) in self.replicas.iter().enumerate() {
+            match tokio::time::timeout(REPLICA_CHECK_TIMEOUT, get_context(&replica.conn)).await {
+                Ok(Ok(context)) => {
+                    replica.slot.store(context.slot, Ordering::Relaxed);
+                    replica.reachable.store(true, Ordering::Relaxed);
+                }
+                Ok(Err(e)) => {
+                    warn!("Read replica {} failed its health check: {}", i, e);
+                    replica.reachable.store(false, Ordering::Relaxed);
+                }
+                Err(_) => {
+                    warn!("Read replica {} timed out on its health check", i);
+                    replica.reachable.store(false, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
+
+/// Checks the primary and the replicas periodically until the databases are dropped.
+fn spawn_slot_checks(databases: Weak<Databases>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REPLICA_CHECK_INTERVAL);
+        // The first tick completes immediately, right after the check done on connecting.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            match databases.upgrade() {
+                Some(databases) => databases.check_slots().await,
+                None => break,
+            }
+        }
+    });
+}
+
+fn connect_options(url: &str, config: &Config) -> Result<PgConnectOptions, DasApiError> {
+    let options: PgConnectOptions = url.parse()?;
+    Ok(match config.statement_timeout_ms {
+        Some(timeout) => options.options([("statement_timeout", timeout.to_string())]),
+        None => options,
+    })
+}
+
+fn pool_options(config: &Config) -> PgPoolOptions {
+    let options = PgPoolOptions::new()
+        .max_connections(
+            config
+                .max_database_connections
+                .unwrap_or(DEFAULT_MAX_CONNECTIONS),
+        )
+        .min_connections(config.min_database_connections.unwrap_or_default());
+    // Passing `None` would turn off the sqlx default instead of keeping it.
+    match config.idle_timeout_seconds {
+        Some(seconds) => options.idle_timeout(Duration::from_secs(seconds)),
+        None => options,
+    }
+}
@@ -1,17 +1,26 @@
 use crate::error::DasApiError;
 use async_trait::async_trait;
-use digital_asset_types::rpc::filter::{AssetSortDirection, SearchConditionType};
+use digital_asset_types::rpc::filter::{
+    AssetSortDirection, AttributeFilter, SearchConditionType, SearchFilter, TextMatchMode,
+};
 use digital_asset_types::rpc::options::Options;
-use digital_asset_types::rpc::response::{AssetList, TransactionSignatureList};
+use digital_asset_types::rpc::response::{
+    AssetHistory, AssetList, CollectionHolderList, CollectionStats, IndexerHealth, IndexerStatus,
+    OwnerCollections, RpcContext, TokenAccountList, TransactionSignatureList, TreeInfo,
+};
 use digital_asset_types::rpc::{filter::AssetSorting, response::GetGroupingResponse};
 use digital_asset_types::rpc::{Asset, AssetProof, Interface, OwnershipModel, RoyaltyModel};
 use open_rpc_derive::{document_rpc, rpc};
 use open_rpc_schema::schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::broadcast;
 
 mod api_impl;
+mod asset_updates;
+mod database;
 pub use api_impl::*;
+pub use asset_updates::{Subscription, SubscriptionKey, UpdatedAsset};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
@@ -32,7 +41,26 @@ pub struct GetAssetsByGroup {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetAssetsByOwner {
-    pub owner_address: String,
+    #[serde(default)]
+    pub owner_address: Option<String>,
+    pub sort_by: Option<AssetSorting>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    #[serde(default, alias = "displayOptions")]
+    pub options: Option<Options>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Lists the assets of several owners together, instead of `owner_address`.
+    #[serde(default)]
+    pub owner_addresses: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetAssetsByDelegate {
+    pub delegate_address: String,
     pub sort_by: Option<AssetSorting>,
     pub limit: Option<u32>,
     pub page: Option<u32>,
@@ -60,22 +88,41 @@ pub struct GetAssets {
     pub options: Option<Options>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetAssetProof {
     pub id: String,
+    /// Builds the proof as it was right after this tree sequence number.
+    #[serde(default)]
+    pub seq: Option<u64>,
+    /// Builds the proof as it was when the tree had this root.
+    #[serde(default)]
+    pub root: Option<String>,
+    /// Leaves out the proof nodes stored in the tree's canopy.
+    #[serde(default)]
+    pub truncate_canopy: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetAssetProofs {
     pub ids: Vec<String>,
+    /// Builds the proofs as they were right after this tree sequence number.
+    #[serde(default)]
+    pub seq: Option<u64>,
+    /// Builds the proofs as they were when the tree had this root.
+    #[serde(default)]
+    pub root: Option<String>,
+    /// Leaves out the proof nodes stored in the trees' canopies.
+    #[serde(default)]
+    pub truncate_canopy: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetAssetsByCreator {
-    pub creator_address: String,
+    #[serde(default)]
+    pub creator_address: Option<String>,
     pub only_verified: Option<bool>,
     pub sort_by: Option<AssetSorting>,
     pub limit: Option<u32>,
@@ -86,6 +133,9 @@ pub struct GetAssetsByCreator {
     pub options: Option<Options>,
     #[serde(default)]
     pub cursor: Option<String>,
+    /// Lists the assets of several creators together, instead of `creator_address`.
+    #[serde(default)]
+    pub creator_addresses: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
@@ -123,6 +173,25 @@ pub struct SearchAssets {
     pub cursor: Option<String>,
     #[serde(default)]
     pub name: Option<String>,
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub text_match_mode: Option<TextMatchMode>,
+    #[serde(default)]
+    pub case_insensitive: Option<bool>,
+    #[serde(default)]
+    pub attributes: Option<AttributeFilter>,
+    /// Filter tree the assets must match, combined with the flat filters above.
+    #[serde(default)]
+    pub filter: Option<SearchFilter>,
+    #[serde(default)]
+    pub owner_addresses: Option<Vec<String>>,
+    #[serde(default)]
+    pub creator_addresses: Option<Vec<String>>,
+    #[serde(default)]
+    pub authority_addresses: Option<Vec<String>>,
+    #[serde(default)]
+    pub groupings: Option<Vec<(String, String)>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -140,6 +209,20 @@ pub struct GetAssetsByAuthority {
     pub cursor: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetAssetsByTree {
+    pub tree: String,
+    pub from_leaf: Option<u64>,
+    pub to_leaf: Option<u64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
+    #[serde(default, alias = "displayOptions")]
+    pub options: Option<Options>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetGrouping {
@@ -161,12 +244,106 @@ pub struct GetAssetSignatures {
     pub cursor: Option<String>,
     #[serde(default)]
     pub sort_direction: Option<AssetSortDirection>,
+    #[serde(default)]
+    pub instruction: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetAssetHistory {
+    pub id: String,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub sort_direction: Option<AssetSortDirection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetCollectionStats {
+    pub group_value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetCollectionHolders {
+    pub group_value: String,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetOwnerCollections {
+    pub owner_address: String,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetTreeInfo {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetTokenAccounts {
+    pub owner_address: Option<String>,
+    pub mint_address: Option<String>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct AssetSubscribe {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct OwnerSubscribe {
+    pub owner_address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GroupSubscribe {
+    pub group_key: String,
+    pub group_value: String,
 }
 
 #[document_rpc]
 #[async_trait]
 pub trait ApiContract: Send + Sync + 'static {
-    async fn check_health(&self) -> Result<(), DasApiError>;
+    /// How far behind the ingester streams are, failing when the index is unhealthy.
+    async fn check_health(&self) -> Result<IndexerHealth, DasApiError>;
+    /// Picks the database a request reads from, preferring replicas that have caught up with
+    /// `min_context_slot`, and the context the response is served in. Fails if even the primary
+    /// is behind `min_context_slot`. Without one, the context is the slot the database was at
+    /// when last checked.
+    async fn reader(
+        &self,
+        min_context_slot: Option<u64>,
+    ) -> Result<(RpcContext, Box<dyn ApiContract>), DasApiError>;
+    /// Assets written by the ingester, loaded once their changes are committed. Only the assets
+    /// matching a live subscription are loaded; the subscription to `key` lasts until the
+    /// returned [Subscription] is dropped.
+    fn subscribe_asset_updates(
+        &self,
+        key: SubscriptionKey,
+    ) -> (broadcast::Receiver<Arc<UpdatedAsset>>, Subscription);
     #[rpc(
         name = "getAssetProof",
         params = "named",
@@ -203,6 +380,15 @@ pub trait ApiContract: Send + Sync + 'static {
         &self,
         payload: GetAssetsByOwner,
     ) -> Result<AssetList, DasApiError>;
+    #[rpc(
+        name = "getAssetsByDelegate",
+        params = "named",
+        summary = "Get a list of assets delegated to an address"
+    )]
+    async fn get_assets_by_delegate(
+        &self,
+        payload: GetAssetsByDelegate,
+    ) -> Result<AssetList, DasApiError>;
     #[rpc(
         name = "getAssetsByGroup",
         params = "named",
@@ -230,6 +416,12 @@ pub trait ApiContract: Send + Sync + 'static {
         &self,
         payload: GetAssetsByAuthority,
     ) -> Result<AssetList, DasApiError>;
+    #[rpc(
+        name = "getAssetsByTree",
+        params = "named",
+        summary = "Get the assets of a compression tree, ordered by leaf index"
+    )]
+    async fn get_assets_by_tree(&self, payload: GetAssetsByTree) -> Result<AssetList, DasApiError>;
     #[rpc(
         name = "searchAssets",
         params = "named",
@@ -245,10 +437,67 @@ pub trait ApiContract: Send + Sync + 'static {
         &self,
         payload: GetAssetSignatures,
     ) -> Result<TransactionSignatureList, DasApiError>;
+    #[rpc(
+        name = "getAssetHistory",
+        params = "named",
+        summary = "Get the decoded event history of a compressed asset"
+    )]
+    async fn get_asset_history(
+        &self,
+        payload: GetAssetHistory,
+    ) -> Result<AssetHistory, DasApiError>;
+    #[rpc(
+        name = "getTreeInfo",
+        params = "named",
+        summary = "Get the indexed state of a compression tree"
+    )]
+    async fn get_tree_info(&self, payload: GetTreeInfo) -> Result<TreeInfo, DasApiError>;
+    #[rpc(
+        name = "getIndexerStatus",
+        params = "named",
+        summary = "Get how far behind the chain the index is"
+    )]
+    async fn get_indexer_status(&self) -> Result<IndexerStatus, DasApiError>;
     #[rpc(
         name = "getGrouping",
         params = "named",
         summary = "Get a list of assets grouped by a specific authority"
     )]
     async fn get_grouping(&self, payload: GetGrouping) -> Result<GetGroupingResponse, DasApiError>;
+    #[rpc(
+        name = "getCollectionStats",
+        params = "named",
+        summary = "Get membership, holder and mint statistics for a verified collection"
+    )]
+    async fn get_collection_stats(
+        &self,
+        payload: GetCollectionStats,
+    ) -> Result<CollectionStats, DasApiError>;
+    #[rpc(
+        name = "getCollectionHolders",
+        params = "named",
+        summary = "Get the owners of a verified collection and how many of its assets they hold"
+    )]
+    async fn get_collection_holders(
+        &self,
+        payload: GetCollectionHolders,
+    ) -> Result<CollectionHolderList, DasApiError>;
+    #[rpc(
+        name = "getOwnerCollections",
+        params = "named",
+        summary = "Get the collections an owner holds assets in, with counts"
+    )]
+    async fn get_owner_collections(
+        &self,
+        payload: GetOwnerCollections,
+    ) -> Result<OwnerCollections, DasApiError>;
+    #[rpc(
+        name = "getTokenAccounts",
+        params = "named",
+        summary = "Get a list of token accounts by owner and/or mint"
+    )]
+    async fn get_token_accounts(
+        &self,
+        payload: GetTokenAccounts,
+    ) -> Result<TokenAccountList, DasApiError>;
 }
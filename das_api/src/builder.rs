@@ -1,7 +1,22 @@
-use jsonrpsee::RpcModule;
-use log::debug;
+use std::sync::Arc;
 
-use crate::{api::*, error::DasApiError};
+use digital_asset_types::rpc::response::{RpcContext, RpcResponse};
+use jsonrpsee::{
+    core::Error as RpcError,
+    types::{error::CallError, Params},
+    RpcModule, SubscriptionSink,
+};
+use log::{debug, warn};
+use serde::de::DeserializeOwned;
+use tokio::sync::broadcast;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    StreamExt,
+};
+
+use crate::{api::*, error::DasApiError, validation::validate_pubkey};
+
+const MIN_CONTEXT_SLOT: &str = "minContextSlot";
 
 pub struct RpcApiBuilder;
 
@@ -15,35 +30,44 @@ impl RpcApiBuilder {
             rpc_context.check_health().await.map_err(Into::into)
         })?;
 
+        module.register_async_method(
+            "getIndexerStatus",
+            |_rpc_params, rpc_context| async move {
+                rpc_context.get_indexer_status().await.map_err(Into::into)
+            },
+        )?;
+
         module.register_async_method("get_asset_proof", |rpc_params, rpc_context| async move {
-            let payload = rpc_params.parse::<GetAssetProof>()?;
-            rpc_context
-                .get_asset_proof(payload)
-                .await
-                .map_err(Into::into)
+            let (payload, context, api) =
+                parse_with_context::<GetAssetProof>(rpc_params, &**rpc_context).await?;
+            let value = api.get_asset_proof(payload).await?;
+            Ok(RpcResponse { context, value })
         })?;
         module.register_alias("getAssetProof", "get_asset_proof")?;
 
         module.register_async_method("get_asset_proofs", |rpc_params, rpc_context| async move {
-            let payload = rpc_params.parse::<GetAssetProofs>()?;
-            rpc_context
-                .get_asset_proofs(payload)
-                .await
-                .map_err(Into::into)
+            let (payload, context, api) =
+                parse_with_context::<GetAssetProofs>(rpc_params, &**rpc_context).await?;
+            let value = api.get_asset_proofs(payload).await?;
+            Ok(RpcResponse { context, value })
         })?;
         module.register_alias("getAssetProofs", "get_asset_proofs")?;
         module.register_alias("get_asset_proof_batch", "get_asset_proofs")?;
         module.register_alias("getAssetProofBatch", "get_asset_proofs")?;
 
         module.register_async_method("get_asset", |rpc_params, rpc_context| async move {
-            let payload = rpc_params.parse::<GetAsset>()?;
-            rpc_context.get_asset(payload).await.map_err(Into::into)
+            let (payload, context, api) =
+                parse_with_context::<GetAsset>(rpc_params, &**rpc_context).await?;
+            let value = api.get_asset(payload).await?;
+            Ok(RpcResponse { context, value })
         })?;
         module.register_alias("getAsset", "get_asset")?;
 
         module.register_async_method("get_assets", |rpc_params, rpc_context| async move {
-            let payload = rpc_params.parse::<GetAssets>()?;
-            rpc_context.get_assets(payload).await.map_err(Into::into)
+            let (payload, context, api) =
+                parse_with_context::<GetAssets>(rpc_params, &**rpc_context).await?;
+            let value = api.get_assets(payload).await?;
+            Ok(RpcResponse { context, value })
         })?;
         module.register_alias("getAssets", "get_assets")?;
         module.register_alias("get_asset_batch", "get_assets")?;
@@ -52,23 +76,32 @@ impl RpcApiBuilder {
         module.register_async_method(
             "get_assets_by_owner",
             |rpc_params, rpc_context| async move {
-                let payload = rpc_params.parse::<GetAssetsByOwner>()?;
-                rpc_context
-                    .get_assets_by_owner(payload)
-                    .await
-                    .map_err(Into::into)
+                let (payload, context, api) =
+                    parse_with_context::<GetAssetsByOwner>(rpc_params, &**rpc_context).await?;
+                let value = api.get_assets_by_owner(payload).await?;
+                Ok(RpcResponse { context, value })
             },
         )?;
         module.register_alias("getAssetsByOwner", "get_assets_by_owner")?;
 
+        module.register_async_method(
+            "get_assets_by_delegate",
+            |rpc_params, rpc_context| async move {
+                let (payload, context, api) =
+                    parse_with_context::<GetAssetsByDelegate>(rpc_params, &**rpc_context).await?;
+                let value = api.get_assets_by_delegate(payload).await?;
+                Ok(RpcResponse { context, value })
+            },
+        )?;
+        module.register_alias("getAssetsByDelegate", "get_assets_by_delegate")?;
+
         module.register_async_method(
             "get_assets_by_creator",
             |rpc_params, rpc_context| async move {
-                let payload = rpc_params.parse::<GetAssetsByCreator>()?;
-                rpc_context
-                    .get_assets_by_creator(payload)
-                    .await
-                    .map_err(Into::into)
+                let (payload, context, api) =
+                    parse_with_context::<GetAssetsByCreator>(rpc_params, &**rpc_context).await?;
+                let value = api.get_assets_by_creator(payload).await?;
+                Ok(RpcResponse { context, value })
             },
         )?;
         module.register_alias("getAssetsByCreator", "get_assets_by_creator")?;
@@ -76,48 +109,269 @@ impl RpcApiBuilder {
         module.register_async_method(
             "getAssetsByAuthority",
             |rpc_params, rpc_context| async move {
-                let payload = rpc_params.parse::<GetAssetsByAuthority>()?;
-                rpc_context
-                    .get_assets_by_authority(payload)
-                    .await
-                    .map_err(Into::into)
+                let (payload, context, api) =
+                    parse_with_context::<GetAssetsByAuthority>(rpc_params, &**rpc_context).await?;
+                let value = api.get_assets_by_authority(payload).await?;
+                Ok(RpcResponse { context, value })
             },
         )?;
 
         module.register_async_method(
             "get_assets_by_group",
             |rpc_params, rpc_context| async move {
-                let payload = rpc_params.parse::<GetAssetsByGroup>()?;
-                rpc_context
-                    .get_assets_by_group(payload)
-                    .await
-                    .map_err(Into::into)
+                let (payload, context, api) =
+                    parse_with_context::<GetAssetsByGroup>(rpc_params, &**rpc_context).await?;
+                let value = api.get_assets_by_group(payload).await?;
+                Ok(RpcResponse { context, value })
             },
         )?;
         module.register_alias("getAssetsByGroup", "get_assets_by_group")?;
 
+        module.register_async_method(
+            "get_assets_by_tree",
+            |rpc_params, rpc_context| async move {
+                let (payload, context, api) =
+                    parse_with_context::<GetAssetsByTree>(rpc_params, &**rpc_context).await?;
+                let value = api.get_assets_by_tree(payload).await?;
+                Ok(RpcResponse { context, value })
+            },
+        )?;
+        module.register_alias("getAssetsByTree", "get_assets_by_tree")?;
+
         module.register_async_method(
             "getAssetSignatures",
             |rpc_params, rpc_context| async move {
-                let payload = rpc_params.parse::<GetAssetSignatures>()?;
-                rpc_context
-                    .get_asset_signatures(payload)
-                    .await
-                    .map_err(Into::into)
+                let (payload, context, api) =
+                    parse_with_context::<GetAssetSignatures>(rpc_params, &**rpc_context).await?;
+                let value = api.get_asset_signatures(payload).await?;
+                Ok(RpcResponse { context, value })
             },
         )?;
         module.register_alias("getSignaturesForAsset", "getAssetSignatures")?;
 
+        module.register_async_method("getAssetHistory", |rpc_params, rpc_context| async move {
+            let (payload, context, api) =
+                parse_with_context::<GetAssetHistory>(rpc_params, &**rpc_context).await?;
+            let value = api.get_asset_history(payload).await?;
+            Ok(RpcResponse { context, value })
+        })?;
+
+        module.register_async_method("getGrouping", |rpc_params, rpc_context| async move {
+            let (payload, context, api) =
+                parse_with_context::<GetGrouping>(rpc_params, &**rpc_context).await?;
+            let value = api.get_grouping(payload).await?;
+            Ok(RpcResponse { context, value })
+        })?;
+
+        module.register_async_method(
+            "getCollectionStats",
+            |rpc_params, rpc_context| async move {
+                let (payload, context, api) =
+                    parse_with_context::<GetCollectionStats>(rpc_params, &**rpc_context).await?;
+                let value = api.get_collection_stats(payload).await?;
+                Ok(RpcResponse { context, value })
+            },
+        )?;
+
+        module.register_async_method(
+            "getCollectionHolders",
+            |rpc_params, rpc_context| async move {
+                let (payload, context, api) =
+                    parse_with_context::<GetCollectionHolders>(rpc_params, &**rpc_context).await?;
+                let value = api.get_collection_holders(payload).await?;
+                Ok(RpcResponse { context, value })
+            },
+        )?;
+
+        module.register_async_method(
+            "getOwnerCollections",
+            |rpc_params, rpc_context| async move {
+                let (payload, context, api) =
+                    parse_with_context::<GetOwnerCollections>(rpc_params, &**rpc_context).await?;
+                let value = api.get_owner_collections(payload).await?;
+                Ok(RpcResponse { context, value })
+            },
+        )?;
+
+        module.register_async_method("getTreeInfo", |rpc_params, rpc_context| async move {
+            let (payload, context, api) =
+                parse_with_context::<GetTreeInfo>(rpc_params, &**rpc_context).await?;
+            let value = api.get_tree_info(payload).await?;
+            Ok(RpcResponse { context, value })
+        })?;
+
         module.register_async_method("search_assets", |rpc_params, rpc_context| async move {
-            let payload = rpc_params.parse::<SearchAssets>()?;
-            rpc_context.search_assets(payload).await.map_err(Into::into)
+            let (payload, context, api) =
+                parse_with_context::<SearchAssets>(rpc_params, &**rpc_context).await?;
+            let value = api.search_assets(payload).await?;
+            Ok(RpcResponse { context, value })
         })?;
         module.register_alias("searchAssets", "search_assets")?;
 
+        module.register_async_method(
+            "get_token_accounts",
+            |rpc_params, rpc_context| async move {
+                let (payload, context, api) =
+                    parse_with_context::<GetTokenAccounts>(rpc_params, &**rpc_context).await?;
+                let value = api.get_token_accounts(payload).await?;
+                Ok(RpcResponse { context, value })
+            },
+        )?;
+        module.register_alias("getTokenAccounts", "get_token_accounts")?;
+
         module.register_async_method("schema", |_, rpc_context| async move {
             Ok(rpc_context.schema())
         })?;
 
+        module.register_subscription(
+            "assetSubscribe",
+            "assetNotification",
+            "assetUnsubscribe",
+            |rpc_params, mut sink, rpc_context| {
+                let id = match rpc_params
+                    .parse::<AssetSubscribe>()
+                    .map_err(RpcError::from)
+                    .and_then(|payload| validate_pubkey(payload.id).map_err(RpcError::from))
+                {
+                    Ok(id) => id,
+                    Err(e) => {
+                        sink.reject(e)?;
+                        return Ok(());
+                    }
+                };
+                let updates = rpc_context
+                    .subscribe_asset_updates(SubscriptionKey::Id(id.to_bytes().to_vec()));
+                let id = id.to_string();
+                pipe_asset_updates(sink, updates, move |update| update.asset.id == id);
+                Ok(())
+            },
+        )?;
+
+        // Matches both the current and the previous owner of each updated asset, so a wallet also
+        // hears about the assets transferred away from it.
+        module.register_subscription(
+            "ownerSubscribe",
+            "ownerNotification",
+            "ownerUnsubscribe",
+            |rpc_params, mut sink, rpc_context| {
+                let owner = match rpc_params
+                    .parse::<OwnerSubscribe>()
+                    .map_err(RpcError::from)
+                    .and_then(|payload| {
+                        validate_pubkey(payload.owner_address).map_err(RpcError::from)
+                    }) {
+                    Ok(owner) => owner,
+                    Err(e) => {
+                        sink.reject(e)?;
+                        return Ok(());
+                    }
+                };
+                let updates = rpc_context
+                    .subscribe_asset_updates(SubscriptionKey::Owner(owner.to_bytes().to_vec()));
+                let owner = owner.to_string();
+                pipe_asset_updates(sink, updates, move |update| {
+                    update.asset.ownership.owner == owner
+                        || update.previous_owner.as_ref() == Some(&owner)
+                });
+                Ok(())
+            },
+        )?;
+
+        module.register_subscription(
+            "groupSubscribe",
+            "groupNotification",
+            "groupUnsubscribe",
+            |rpc_params, mut sink, rpc_context| {
+                let GroupSubscribe {
+                    group_key,
+                    group_value,
+                } = match rpc_params.parse::<GroupSubscribe>() {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        sink.reject(e)?;
+                        return Ok(());
+                    }
+                };
+                let updates = rpc_context.subscribe_asset_updates(SubscriptionKey::Group {
+                    key: group_key.clone(),
+                    value: group_value.clone(),
+                });
+                pipe_asset_updates(sink, updates, move |update| {
+                    update.asset.grouping.iter().flatten().any(|group| {
+                        group.group_key == group_key
+                            && group.group_value.as_ref() == Some(&group_value)
+                    })
+                });
+                Ok(())
+            },
+        )?;
+
         Ok(module)
     }
 }
+
+/// Parses the params of a method along with the `minContextSlot` every method accepts as a named
+/// param, and picks the api to run the method on and the context its result is served in.
+async fn parse_with_context<T: DeserializeOwned>(
+    params: Params<'_>,
+    contract: &dyn ApiContract,
+) -> Result<(T, RpcContext, Box<dyn ApiContract>), RpcError> {
+    let mut params: serde_json::Value = params.parse()?;
+    let positional_min_context_slot = params.as_array().is_some_and(|params| {
+        params
+            .iter()
+            .filter_map(|param| param.as_object())
+            .any(|param| param.contains_key(MIN_CONTEXT_SLOT))
+    });
+    if positional_min_context_slot {
+        return Err(CallError::InvalidParams(
+            DasApiError::ValidationError(format!(
+                "{} is only accepted as a named param",
+                MIN_CONTEXT_SLOT
+            ))
+            .into(),
+        )
+        .into());
+    }
+    let min_context_slot: Option<u64> = take_named_param(&mut params, MIN_CONTEXT_SLOT)?;
+    let payload = serde_json::from_value(params).map_err(|e| CallError::InvalidParams(e.into()))?;
+    let (context, api) = contract.reader(min_context_slot).await?;
+    Ok((payload, context, api))
+}
+
+fn take_named_param<V: DeserializeOwned>(
+    params: &mut serde_json::Value,
+    key: &str,
+) -> Result<Option<V>, CallError> {
+    params
+        .as_object_mut()
+        .and_then(|params| params.remove(key))
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| CallError::InvalidParams(e.into()))
+}
+
+/// Forwards the asset updates accepted by `matches` to the subscriber until it unsubscribes.
+fn pipe_asset_updates<F>(
+    mut sink: SubscriptionSink,
+    (updates, subscription): (broadcast::Receiver<Arc<UpdatedAsset>>, Subscription),
+    matches: F,
+) where
+    F: Fn(&UpdatedAsset) -> bool + Send + 'static,
+{
+    let assets = BroadcastStream::new(updates).filter_map(move |update| match update {
+        Ok(update) if matches(&update) => Some(update.asset.clone()),
+        Ok(_) => None,
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            warn!(
+                "Subscriber lagged behind, skipped {} asset updates",
+                skipped
+            );
+            None
+        }
+    });
+    tokio::spawn(async move {
+        sink.pipe_from_stream(assets).await;
+        drop(subscription);
+    });
+}
@@ -7,10 +7,27 @@ use {
 #[derive(Deserialize, Default)]
 pub struct Config {
     pub database_url: String,
+    /// Read replicas of `database_url`, given as a list, e.g. `["postgres://...", "postgres://..."]`.
+    #[serde(default)]
+    pub replica_database_urls: Vec<String>,
+    /// Connections per database, 250 by default.
+    pub max_database_connections: Option<u32>,
+    pub min_database_connections: Option<u32>,
+    /// Seconds before an unused connection above the minimum is closed.
+    pub idle_timeout_seconds: Option<u64>,
+    pub statement_timeout_ms: Option<u64>,
+    /// Slots a replica may trail the primary by before reads stop going to it, 150 by default.
+    pub max_replica_lag_slots: Option<u64>,
     pub metrics_port: Option<u16>,
     pub metrics_host: Option<String>,
     pub server_port: u16,
     pub env: Option<String>,
+    /// Slots a stream may fall behind the others before the node reports itself unhealthy.
+    pub max_indexer_lag_slots: Option<u64>,
+    /// Seconds a stream may go without progress before the node reports itself unhealthy.
+    pub max_indexer_lag_seconds: Option<u64>,
+    /// Messages a stream may have waiting before the node reports itself unhealthy.
+    pub max_indexer_backlog: Option<u64>,
 }
 
 pub fn load_config() -> Result<Config, DasApiError> {
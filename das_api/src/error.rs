@@ -1,6 +1,14 @@
 use log::{debug, error};
 
-use {jsonrpsee::core::Error as RpcError, jsonrpsee::types::error::CallError, thiserror::Error};
+use {
+    jsonrpsee::core::Error as RpcError,
+    jsonrpsee::types::error::{CallError, ErrorObject},
+    serde_json::json,
+    thiserror::Error,
+};
+
+/// Error code Solana RPC uses when a request's `minContextSlot` is ahead of the node.
+const MIN_CONTEXT_SLOT_NOT_REACHED_CODE: i32 = -32016;
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
@@ -31,6 +39,12 @@ pub enum DasApiError {
     CursorValidationError(String),
     #[error("Pagination Sorting Error. Only sorting based on id is supported for this pagination option.")]
     PaginationSortingValidationError,
+    #[error("Pagination Error. Before and after are not supported for this query, use a cursor or page instead.")]
+    PaginationKeysetUnsupportedError,
+    #[error("Minimum context slot has not been reached, the index is at slot {0}")]
+    MinContextSlotNotReached(u64),
+    #[error("Indexer Unhealthy: {0}")]
+    UnhealthyIndexerError(String),
 }
 
 impl From<DasApiError> for RpcError {
@@ -39,6 +53,14 @@ impl From<DasApiError> for RpcError {
             DasApiError::ValidationError(_) => {
                 debug!("{}", error);
             }
+            DasApiError::MinContextSlotNotReached(context_slot) => {
+                debug!("{}", error);
+                return RpcError::Call(CallError::Custom(ErrorObject::owned(
+                    MIN_CONTEXT_SLOT_NOT_REACHED_CODE,
+                    error.to_string(),
+                    Some(json!({ "contextSlot": context_slot })),
+                )));
+            }
             _ => {
                 error!("{}", error);
             }
@@ -1,28 +1,34 @@
 use crate::error::DasApiError;
+use digital_asset_types::{
+    dao::{
+        sea_orm_active_enums::{OwnerType, RoyaltyTargetType},
+        AssetFilter, Cursor,
+    },
+    rpc::{filter::SearchFilter, OwnershipModel, RoyaltyModel},
+};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
+/// Most addresses a single address filter accepts.
+const MAX_ADDRESSES: usize = 1000;
+
+/// Deepest nesting of groups a search filter tree may have.
+const MAX_FILTER_DEPTH: usize = 8;
+
+/// Most nodes, groups included, a search filter tree may have.
+const MAX_FILTER_NODES: usize = 100;
+
+/// Bytes in the cursors of methods paged by an integer key, such as a leaf index or a sequence
+/// number.
+pub const INT_CURSOR_LEN: usize = 8;
+
+/// Bytes in the cursors of uncompressed asset signatures, which hold a slot and a row id.
+pub const SLOT_AND_ID_CURSOR_LEN: usize = 16;
+
 pub fn validate_pubkey(str_pubkey: String) -> Result<Pubkey, DasApiError> {
     Pubkey::from_str(&str_pubkey).map_err(|_| DasApiError::PubkeyValidationError(str_pubkey))
 }
 
-pub fn validate_search_with_name(
-    name: &Option<String>,
-    owner: &Option<Vec<u8>>,
-) -> Result<Option<Vec<u8>>, DasApiError> {
-    let opt_name = if let Some(n) = name {
-        if owner.is_none() {
-            return Err(DasApiError::ValidationError(
-                "Owner address must be provided in order to search assets by name".to_owned(),
-            ));
-        }
-        Some(n.clone().into_bytes())
-    } else {
-        None
-    };
-    Ok(opt_name)
-}
-
 pub fn validate_opt_pubkey(pubkey: &Option<String>) -> Result<Option<Vec<u8>>, DasApiError> {
     let opt_bytes = if let Some(pubkey) = pubkey {
         let pubkey = Pubkey::from_str(pubkey)
@@ -33,3 +39,117 @@ pub fn validate_opt_pubkey(pubkey: &Option<String>) -> Result<Option<Vec<u8>>, D
     };
     Ok(opt_bytes)
 }
+
+/// Checks that a cursor holds a key of one of the lengths the method issues.
+pub fn validate_cursor_key(cursor: &Option<Cursor>, lengths: &[usize]) -> Result<(), DasApiError> {
+    match cursor.as_ref().and_then(|cursor| cursor.id.as_ref()) {
+        Some(id) if !lengths.contains(&id.len()) => Err(DasApiError::PaginationError),
+        _ => Ok(()),
+    }
+}
+
+/// Validates an address filter given either as a single address or as a list of them.
+pub fn validate_addresses(
+    address: &Option<String>,
+    addresses: &Option<Vec<String>>,
+    field: &str,
+    list_field: &str,
+) -> Result<Option<Vec<Vec<u8>>>, DasApiError> {
+    match (address, addresses) {
+        (Some(_), Some(_)) => Err(DasApiError::ValidationError(format!(
+            "Cannot provide both '{}' and '{}'",
+            field, list_field
+        ))),
+        (Some(address), None) => Ok(Some(vec![validate_pubkey(address.clone())?
+            .to_bytes()
+            .to_vec()])),
+        (None, Some(addresses)) if addresses.is_empty() => Err(DasApiError::ValidationError(
+            format!("'{}' must not be empty", list_field),
+        )),
+        (None, Some(addresses)) if addresses.len() > MAX_ADDRESSES => {
+            Err(DasApiError::BatchSizeExceededError)
+        }
+        (None, Some(addresses)) => addresses
+            .iter()
+            .map(|address| validate_pubkey(address.clone()).map(|a| a.to_bytes().to_vec()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Validates a search filter tree, decoding its addresses.
+pub fn validate_search_filter(filter: SearchFilter) -> Result<AssetFilter, DasApiError> {
+    search_filter_node(filter, 0, &mut 0)
+}
+
+fn search_filter_node(
+    filter: SearchFilter,
+    depth: usize,
+    nodes: &mut usize,
+) -> Result<AssetFilter, DasApiError> {
+    if depth > MAX_FILTER_DEPTH {
+        return Err(DasApiError::ValidationError(format!(
+            "Filter groups must not be nested more than {} levels deep",
+            MAX_FILTER_DEPTH
+        )));
+    }
+    *nodes += 1;
+    if *nodes > MAX_FILTER_NODES {
+        return Err(DasApiError::ValidationError(format!(
+            "Filters must not have more than {} nodes",
+            MAX_FILTER_NODES
+        )));
+    }
+    let mut group = |group: Vec<SearchFilter>| {
+        if group.is_empty() {
+            return Err(DasApiError::ValidationError(
+                "Filter groups must not be empty".to_string(),
+            ));
+        }
+        group
+            .into_iter()
+            .map(|node| search_filter_node(node, depth + 1, nodes))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let pubkey = |address: String| validate_pubkey(address).map(|a| a.to_bytes().to_vec());
+    Ok(match filter {
+        SearchFilter::All(nodes) => AssetFilter::All(group(nodes)?),
+        SearchFilter::Any(nodes) => AssetFilter::Any(group(nodes)?),
+        SearchFilter::Not(node) => {
+            AssetFilter::Not(Box::new(search_filter_node(*node, depth + 1, nodes)?))
+        }
+        SearchFilter::Interface(interface) => {
+            let (version, class) = interface.into();
+            AssetFilter::Interface(version, class)
+        }
+        SearchFilter::OwnerAddress(owner) => AssetFilter::Owner(pubkey(owner)?),
+        SearchFilter::OwnerType(owner_type) => AssetFilter::OwnerType(match owner_type {
+            OwnershipModel::Single => OwnerType::Single,
+            OwnershipModel::Token => OwnerType::Token,
+        }),
+        SearchFilter::CreatorAddress(creator) => AssetFilter::Creator(pubkey(creator)?),
+        SearchFilter::CreatorVerified(verified) => AssetFilter::CreatorVerified(verified),
+        SearchFilter::AuthorityAddress(authority) => AssetFilter::Authority(pubkey(authority)?),
+        SearchFilter::Grouping((key, value)) => AssetFilter::Grouping(key, value),
+        SearchFilter::Delegate(delegate) => AssetFilter::Delegate(pubkey(delegate)?),
+        SearchFilter::Frozen(frozen) => AssetFilter::Frozen(frozen),
+        SearchFilter::Supply(supply) => AssetFilter::Supply(supply),
+        SearchFilter::SupplyMint(mint) => AssetFilter::SupplyMint(pubkey(mint)?),
+        SearchFilter::Compressed(compressed) => AssetFilter::Compressed(compressed),
+        SearchFilter::Compressible(compressible) => AssetFilter::Compressible(compressible),
+        SearchFilter::RoyaltyTargetType(target_type) => {
+            AssetFilter::RoyaltyTargetType(match target_type {
+                RoyaltyModel::Creators => RoyaltyTargetType::Creators,
+                RoyaltyModel::Fanout => RoyaltyTargetType::Fanout,
+                RoyaltyModel::Single => RoyaltyTargetType::Single,
+            })
+        }
+        SearchFilter::RoyaltyTarget(target) => AssetFilter::RoyaltyTarget(pubkey(target)?),
+        SearchFilter::RoyaltyAmount(amount) => AssetFilter::RoyaltyAmount(amount),
+        SearchFilter::Burnt(burnt) => AssetFilter::Burnt(burnt),
+        SearchFilter::JsonUri(uri) => AssetFilter::JsonUri(uri),
+        SearchFilter::Name(name) => AssetFilter::Name(name),
+        SearchFilter::Symbol(symbol) => AssetFilter::Symbol(symbol),
+    })
+}
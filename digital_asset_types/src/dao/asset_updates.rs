@@ -0,0 +1,92 @@
+use super::{asset, asset_grouping, extensions};
+use sea_orm::{entity::*, query::*, ConnectionTrait, DbBackend, DbErr, FromQueryResult, Statement};
+
+/// Postgres `LISTEN`/`NOTIFY` channel carrying the base58 id of every asset written by the
+/// ingester, followed by the base58 owner the asset had before when it changed hands.
+pub const ASSET_UPDATES_CHANNEL: &str = "asset_updates";
+
+/// An asset update announced on [ASSET_UPDATES_CHANNEL].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetUpdate {
+    pub id: Vec<u8>,
+    /// The owner the asset was transferred away from, if the update changed its owner.
+    pub previous_owner: Option<Vec<u8>>,
+}
+
+impl AssetUpdate {
+    pub fn parse(payload: &str) -> Result<Self, bs58::decode::Error> {
+        let mut keys = payload.split(' ');
+        let id = bs58::decode(keys.next().unwrap_or_default()).into_vec()?;
+        let previous_owner = keys
+            .next()
+            .map(|owner| bs58::decode(owner).into_vec())
+            .transpose()?;
+        Ok(AssetUpdate { id, previous_owner })
+    }
+}
+
+/// The keys an updated asset can be subscribed to by, one row per grouping of the asset.
+#[derive(FromQueryResult, Debug, Clone, PartialEq, Eq)]
+pub struct AssetUpdateKeys {
+    pub id: Vec<u8>,
+    pub owner: Option<Vec<u8>>,
+    pub group_key: Option<String>,
+    pub group_value: Option<String>,
+}
+
+/// Looks up the subscription keys of the given assets, without loading the rest of them.
+pub async fn get_asset_update_keys<T: ConnectionTrait>(
+    conn: &T,
+    ids: Vec<Vec<u8>>,
+) -> Result<Vec<AssetUpdateKeys>, DbErr> {
+    asset::Entity::find()
+        .select_only()
+        .column(asset::Column::Id)
+        .column(asset::Column::Owner)
+        .column(asset_grouping::Column::GroupKey)
+        .column(asset_grouping::Column::GroupValue)
+        .join(
+            JoinType::LeftJoin,
+            extensions::asset::Relation::AssetGrouping.def(),
+        )
+        .filter(asset::Column::Id.is_in(ids))
+        .into_model::<AssetUpdateKeys>()
+        .all(conn)
+        .await
+}
+
+/// Announces that the asset with the given id has changed.
+///
+/// Postgres holds notifications until the surrounding transaction commits and collapses
+/// duplicates within it, so listeners only ever see committed state and at most one
+/// notification per asset and transaction.
+pub async fn notify_asset_updated<T: ConnectionTrait>(conn: &T, id: &[u8]) -> Result<(), DbErr> {
+    notify(conn, bs58::encode(id).into_string()).await
+}
+
+/// Announces that the asset with the given id was transferred away from `previous_owner`.
+pub async fn notify_asset_transferred<T: ConnectionTrait>(
+    conn: &T,
+    id: &[u8],
+    previous_owner: &[u8],
+) -> Result<(), DbErr> {
+    notify(
+        conn,
+        format!(
+            "{} {}",
+            bs58::encode(id).into_string(),
+            bs58::encode(previous_owner).into_string()
+        ),
+    )
+    .await
+}
+
+async fn notify<T: ConnectionTrait>(conn: &T, payload: String) -> Result<(), DbErr> {
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_notify($1, $2)",
+        [ASSET_UPDATES_CHANNEL.into(), payload.into()],
+    ))
+    .await?;
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use crate::dao::{asset, asset_authority, asset_creators, asset_data, asset_grouping};
+use crate::dao::{
+    asset, asset_authority, asset_creators, asset_data, asset_grouping, asset_signatures,
+    cl_audits_v2, tokens,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FullAsset {
@@ -7,6 +10,9 @@ pub struct FullAsset {
     pub authorities: Vec<asset_authority::Model>,
     pub creators: Vec<asset_creators::Model>,
     pub groups: Vec<asset_grouping::Model>,
+    pub token_info: Option<tokens::Model>,
+    /// Amount of the asset held by the owner the assets were queried for, if any.
+    pub token_balance: Option<i64>,
 }
 #[derive(Clone, Debug, PartialEq)]
 pub struct AssetRelated {
@@ -18,3 +24,11 @@ pub struct AssetRelated {
 pub struct FullAssetList {
     pub list: Vec<FullAsset>,
 }
+
+/// Transactions of an asset. Compressed assets are tracked through their leaf, uncompressed
+/// assets through their mint.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssetSignatures {
+    Compressed(Vec<cl_audits_v2::Model>),
+    Uncompressed(Vec<asset_signatures::Model>),
+}
@@ -22,6 +22,7 @@ pub struct Model {
     pub created_at: DateTime,
     pub tx: Vec<u8>,
     pub instruction: Instruction,
+    pub slot: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -33,6 +34,7 @@ pub enum Column {
     CreatedAt,
     Tx,
     Instruction,
+    Slot,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -61,6 +63,7 @@ impl ColumnTrait for Column {
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::Tx => ColumnType::Binary.def(),
             Self::Instruction => Instruction::db_type(),
+            Self::Slot => ColumnType::BigInteger.def().null(),
         }
     }
 }
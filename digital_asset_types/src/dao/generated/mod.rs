@@ -3,14 +3,21 @@
 pub mod prelude;
 
 pub mod asset;
+pub mod asset_attributes;
 pub mod asset_authority;
 pub mod asset_creators;
 pub mod asset_data;
+pub mod asset_events;
 pub mod asset_grouping;
+pub mod asset_signatures;
 pub mod asset_v1_account_attachments;
 pub mod backfill_items;
 pub mod cl_audits_v2;
 pub mod cl_items;
+pub mod cl_items_history;
+pub mod ingester_failed_messages;
+pub mod ingester_progress;
+pub mod merkle_tree;
 pub mod raw_txn;
 pub mod sea_orm_active_enums;
 pub mod tasks;
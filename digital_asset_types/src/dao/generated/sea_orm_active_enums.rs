@@ -159,3 +159,31 @@ pub enum Instruction {
     #[sea_orm(string_value = "verify_creator")]
     VerifyCreator,
 }
+#[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "asset_event_type")]
+pub enum AssetEventType {
+    #[sea_orm(string_value = "burn")]
+    Burn,
+    #[sea_orm(string_value = "cancel_redeem")]
+    CancelRedeem,
+    #[sea_orm(string_value = "delegate")]
+    Delegate,
+    #[sea_orm(string_value = "mint")]
+    Mint,
+    #[sea_orm(string_value = "redeem")]
+    Redeem,
+    #[sea_orm(string_value = "transfer")]
+    Transfer,
+    #[sea_orm(string_value = "undelegate")]
+    Undelegate,
+    #[sea_orm(string_value = "unverify_collection")]
+    UnverifyCollection,
+    #[sea_orm(string_value = "unverify_creator")]
+    UnverifyCreator,
+    #[sea_orm(string_value = "update_metadata")]
+    UpdateMetadata,
+    #[sea_orm(string_value = "verify_collection")]
+    VerifyCollection,
+    #[sea_orm(string_value = "verify_creator")]
+    VerifyCreator,
+}
@@ -1,24 +1,36 @@
 #![allow(ambiguous_glob_reexports)]
+mod asset_updates;
 mod full_asset;
 mod generated;
 pub mod scopes;
+mod stream_progress;
 use self::sea_orm_active_enums::{
     OwnerType, RoyaltyTargetType, SpecificationAssetClass, SpecificationVersions,
 };
+pub use asset_updates::*;
 pub use full_asset::*;
 pub use generated::*;
+pub use stream_progress::*;
 pub mod extensions;
 
+use crate::rpc::filter::TextMatchMode;
 use sea_orm::{
-    entity::*,
-    sea_query::Expr,
-    sea_query::{ConditionType, IntoCondition, SimpleExpr},
-    Condition, DbErr, RelationDef,
+    entity::{prelude::DateTimeWithTimeZone, *},
+    sea_query::{ConditionType, Expr, IntoCondition, Query, SimpleExpr},
+    Condition, DbErr, FromQueryResult, RelationDef,
 };
 use serde::{Deserialize, Serialize};
 
 pub struct GroupingSize {
     pub size: u64,
+    /// Name of the collection NFT, for collection groupings whose metadata has been indexed.
+    pub name: Option<String>,
+}
+
+#[derive(FromQueryResult, Debug, Clone, PartialEq)]
+pub struct HolderCount {
+    pub owner: Vec<u8>,
+    pub count: i64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -33,6 +45,165 @@ pub struct PageOptions {
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Cursor {
     pub id: Option<Vec<u8>>,
+    /// Value of the sort column for the item the cursor points at. Only set when sorting by a
+    /// column other than the id, which is then used as the tie-breaker.
+    pub sort_value: Option<SortValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SortValue {
+    Null,
+    Int(i64),
+    Timestamp(DateTimeWithTimeZone),
+    Bytes(Vec<u8>),
+}
+
+impl SortValue {
+    /// The value to compare the sort column against, `None` for NULL.
+    pub fn into_value(self) -> Option<Value> {
+        match self {
+            SortValue::Null => None,
+            SortValue::Int(v) => Some(v.into()),
+            SortValue::Timestamp(v) => Some(v.into()),
+            SortValue::Bytes(v) => Some(v.into()),
+        }
+    }
+}
+
+/// What assets can be ordered by, ahead of the id used as the tie-breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Id,
+    CreatedAt,
+    SlotUpdated,
+    /// Latest slot at which any of the asset's accounts or its leaf changed on chain.
+    RecentAction,
+    /// Raw on-chain name, NULL until the asset's metadata has been indexed.
+    Name,
+    RoyaltyAmount,
+    Supply,
+}
+
+impl SortColumn {
+    /// The expression to order and compare `asset` rows by.
+    pub fn expr(self) -> SimpleExpr {
+        match self {
+            SortColumn::Id => Expr::col((asset::Entity, asset::Column::Id)).into(),
+            SortColumn::CreatedAt => Expr::col((asset::Entity, asset::Column::CreatedAt)).into(),
+            SortColumn::SlotUpdated => Expr::col((asset::Entity, asset::Column::SlotUpdated)).into(),
+            // GREATEST ignores NULLs, so this is only NULL for assets never seen on chain.
+            SortColumn::RecentAction => Expr::cust(
+                "GREATEST(asset.slot_updated_metadata_account, asset.slot_updated_mint_account, \
+                 asset.slot_updated_token_account, asset.slot_updated_cnft_transaction)",
+            ),
+            SortColumn::Name => Expr::cust(
+                "(SELECT asset_data.raw_name FROM asset_data WHERE asset_data.id = asset.asset_data)",
+            ),
+            SortColumn::RoyaltyAmount => Expr::col((asset::Entity, asset::Column::RoyaltyAmount)).into(),
+            SortColumn::Supply => Expr::col((asset::Entity, asset::Column::Supply)).into(),
+        }
+    }
+
+    /// The value of the expression for `asset`, `None` when sorting by the id alone.
+    fn value_of(self, asset: &FullAsset) -> Option<SortValue> {
+        let a = &asset.asset;
+        let int = |v: Option<i64>| v.map_or(SortValue::Null, SortValue::Int);
+        match self {
+            SortColumn::Id => None,
+            SortColumn::CreatedAt => {
+                Some(a.created_at.map_or(SortValue::Null, SortValue::Timestamp))
+            }
+            SortColumn::SlotUpdated => Some(int(a.slot_updated)),
+            SortColumn::RecentAction => Some(int([
+                a.slot_updated_metadata_account,
+                a.slot_updated_mint_account,
+                a.slot_updated_token_account,
+                a.slot_updated_cnft_transaction,
+            ]
+            .into_iter()
+            .flatten()
+            .max())),
+            SortColumn::Name => Some(
+                asset
+                    .data
+                    .raw_name
+                    .clone()
+                    .map_or(SortValue::Null, SortValue::Bytes),
+            ),
+            SortColumn::RoyaltyAmount => Some(SortValue::Int(a.royalty_amount.into())),
+            SortColumn::Supply => Some(SortValue::Int(a.supply)),
+        }
+    }
+}
+
+const ID_LEN: usize = 32;
+const SORT_VALUE_NULL: u8 = 0;
+const SORT_VALUE_INT: u8 = 1;
+const SORT_VALUE_TIMESTAMP: u8 = 2;
+const SORT_VALUE_BYTES: u8 = 3;
+
+impl Cursor {
+    /// Builds the cursor pointing at `asset` for a query sorted by `sort_column`.
+    pub fn from_asset(asset: &FullAsset, sort_column: Option<SortColumn>) -> Self {
+        Cursor {
+            id: Some(asset.asset.id.clone()),
+            sort_value: sort_column.and_then(|c| c.value_of(asset)),
+        }
+    }
+
+    /// Encodes the cursor as the opaque string handed out to clients. Cursors without a sort
+    /// value encode to the base58 id alone.
+    pub fn encode(&self) -> Option<String> {
+        let mut bytes = self.id.clone()?;
+        match self.sort_value {
+            None => {}
+            Some(SortValue::Null) => bytes.push(SORT_VALUE_NULL),
+            Some(SortValue::Int(v)) => {
+                bytes.push(SORT_VALUE_INT);
+                bytes.extend_from_slice(&v.to_be_bytes());
+            }
+            Some(SortValue::Timestamp(v)) => {
+                bytes.push(SORT_VALUE_TIMESTAMP);
+                bytes.extend_from_slice(&v.timestamp_micros().to_be_bytes());
+            }
+            Some(SortValue::Bytes(ref v)) => {
+                bytes.push(SORT_VALUE_BYTES);
+                bytes.extend_from_slice(v);
+            }
+        }
+        Some(bs58::encode(bytes).into_string())
+    }
+
+    /// Decodes a cursor produced by [Cursor::encode].
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = bs58::decode(cursor).into_vec().ok()?;
+        if bytes.len() <= ID_LEN {
+            return Some(Cursor {
+                id: Some(bytes),
+                sort_value: None,
+            });
+        }
+
+        let (id, sort_value) = bytes.split_at(ID_LEN);
+        let int = |v: &[u8]| v.try_into().ok().map(i64::from_be_bytes);
+        let sort_value = match sort_value.split_first()? {
+            (&SORT_VALUE_NULL, []) => SortValue::Null,
+            (&SORT_VALUE_INT, v) => SortValue::Int(int(v)?),
+            (&SORT_VALUE_TIMESTAMP, v) => {
+                let naive = chrono::NaiveDateTime::from_timestamp_micros(int(v)?)?;
+                SortValue::Timestamp(chrono::DateTime::from_naive_utc_and_offset(
+                    naive,
+                    chrono::FixedOffset::east_opt(0)?,
+                ))
+            }
+            (&SORT_VALUE_BYTES, v) => SortValue::Bytes(v.to_vec()),
+            _ => return None,
+        };
+        Some(Cursor {
+            id: Some(id.to_vec()),
+            sort_value: Some(sort_value),
+        })
+    }
 }
 
 pub enum Pagination {
@@ -54,12 +225,17 @@ pub struct SearchAssetsQuery {
     pub condition_type: Option<ConditionType>,
     pub specification_version: Option<SpecificationVersions>,
     pub specification_asset_class: Option<SpecificationAssetClass>,
-    pub owner_address: Option<Vec<u8>>,
+    /// Matches assets held by any of the owners.
+    pub owner_addresses: Option<Vec<Vec<u8>>>,
     pub owner_type: Option<OwnerType>,
-    pub creator_address: Option<Vec<u8>>,
+    /// Matches assets with any of the creators.
+    pub creator_addresses: Option<Vec<Vec<u8>>>,
+    /// Applies to the listed creators when there are any, to every creator otherwise.
     pub creator_verified: Option<bool>,
-    pub authority_address: Option<Vec<u8>>,
-    pub grouping: Option<(String, String)>,
+    /// Matches assets with any of the authorities.
+    pub authority_addresses: Option<Vec<Vec<u8>>>,
+    /// Matches assets in any of the `(group_key, group_value)` groups.
+    pub groupings: Option<Vec<(String, String)>>,
     pub delegate: Option<Vec<u8>>,
     pub frozen: Option<bool>,
     pub supply: Option<u64>,
@@ -71,7 +247,195 @@ pub struct SearchAssetsQuery {
     pub royalty_amount: Option<u32>,
     pub burnt: Option<bool>,
     pub json_uri: Option<String>,
-    pub name: Option<Vec<u8>>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    /// How `name` and `symbol` are matched. Defaults to [TextMatchMode::Contains]
+    pub text_match_mode: Option<TextMatchMode>,
+    pub case_insensitive: Option<bool>,
+    /// Off-chain metadata attributes as `(trait_type, value)` pairs
+    pub attributes: Option<Vec<(String, String)>>,
+    /// Defaults to [ConditionType::All]
+    pub attributes_condition_type: Option<ConditionType>,
+    /// Include fungible assets, matching them by token account when searching by owner.
+    pub show_fungible: bool,
+    /// Filter tree the assets must match on top of the flat conditions above.
+    pub filter: Option<AssetFilter>,
+}
+
+/// Node of a [SearchAssetsQuery] filter tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetFilter {
+    All(Vec<AssetFilter>),
+    Any(Vec<AssetFilter>),
+    Not(Box<AssetFilter>),
+    Interface(SpecificationVersions, SpecificationAssetClass),
+    Owner(Vec<u8>),
+    OwnerType(OwnerType),
+    Creator(Vec<u8>),
+    CreatorVerified(bool),
+    Authority(Vec<u8>),
+    Grouping(String, String),
+    Delegate(Vec<u8>),
+    Frozen(bool),
+    Supply(u64),
+    SupplyMint(Vec<u8>),
+    Compressed(bool),
+    Compressible(bool),
+    RoyaltyTargetType(RoyaltyTargetType),
+    RoyaltyTarget(Vec<u8>),
+    RoyaltyAmount(u32),
+    Burnt(bool),
+    JsonUri(String),
+    Name(String),
+    Symbol(String),
+}
+
+impl AssetFilter {
+    /// Whether this node or any node below it satisfies `predicate`.
+    pub fn any_node(&self, predicate: &impl Fn(&AssetFilter) -> bool) -> bool {
+        predicate(self)
+            || match self {
+                AssetFilter::All(nodes) | AssetFilter::Any(nodes) => {
+                    nodes.iter().any(|node| node.any_node(predicate))
+                }
+                AssetFilter::Not(node) => node.any_node(predicate),
+                _ => false,
+            }
+    }
+
+    /// Every node evaluates to true or false, never NULL, so that [AssetFilter::Not] keeps the
+    /// assets a node does not match because they have no value for it.
+    fn condition(&self, query: &SearchAssetsQuery) -> Condition {
+        let leaf = |expr: SimpleExpr| Condition::all().add(expr);
+        let set_and = |column: asset::Column, expr: SimpleExpr| {
+            Condition::all().add(column.is_not_null()).add(expr)
+        };
+        match self {
+            AssetFilter::All(nodes) => nodes.iter().fold(Condition::all(), |cond, node| {
+                cond.add(node.condition(query))
+            }),
+            AssetFilter::Any(nodes) => nodes.iter().fold(Condition::any(), |cond, node| {
+                cond.add(node.condition(query))
+            }),
+            AssetFilter::Not(node) => node.condition(query).not(),
+            AssetFilter::Interface(version, class) => Condition::all()
+                .add(asset::Column::SpecificationVersion.is_not_null())
+                .add(asset::Column::SpecificationAssetClass.is_not_null())
+                .add(asset::Column::SpecificationVersion.eq(version.clone()))
+                .add(asset::Column::SpecificationAssetClass.eq(class.clone())),
+            AssetFilter::Owner(owner) => {
+                scopes::asset::owner_condition(vec![owner.clone()], query.show_fungible)
+            }
+            AssetFilter::OwnerType(owner_type) => {
+                leaf(asset::Column::OwnerType.eq(owner_type.clone()))
+            }
+            AssetFilter::Creator(creator) => {
+                leaf(creator_assets(Some(vec![creator.clone()]), None))
+            }
+            AssetFilter::CreatorVerified(verified) => leaf(creator_assets(None, Some(*verified))),
+            AssetFilter::Authority(authority) => leaf(authority_assets(vec![authority.clone()])),
+            AssetFilter::Grouping(key, value) => {
+                leaf(grouping_assets(vec![(key.clone(), value.clone())]))
+            }
+            AssetFilter::Delegate(delegate) => set_and(
+                asset::Column::Delegate,
+                asset::Column::Delegate.eq(delegate.clone()),
+            ),
+            AssetFilter::Frozen(frozen) => leaf(asset::Column::Frozen.eq(*frozen)),
+            AssetFilter::Supply(supply) => leaf(asset::Column::Supply.eq(*supply)),
+            AssetFilter::SupplyMint(mint) => set_and(
+                asset::Column::SupplyMint,
+                asset::Column::SupplyMint.eq(mint.clone()),
+            ),
+            AssetFilter::Compressed(compressed) => leaf(asset::Column::Compressed.eq(*compressed)),
+            AssetFilter::Compressible(compressible) => {
+                leaf(asset::Column::Compressible.eq(*compressible))
+            }
+            AssetFilter::RoyaltyTargetType(target_type) => {
+                leaf(asset::Column::RoyaltyTargetType.eq(target_type.clone()))
+            }
+            AssetFilter::RoyaltyTarget(target) => set_and(
+                asset::Column::RoyaltyTarget,
+                asset::Column::RoyaltyTarget.eq(target.clone()),
+            ),
+            AssetFilter::RoyaltyAmount(amount) => leaf(asset::Column::RoyaltyAmount.eq(*amount)),
+            AssetFilter::Burnt(burnt) => leaf(asset::Column::Burnt.eq(*burnt)),
+            AssetFilter::JsonUri(uri) => leaf(asset_data::Column::MetadataUrl.eq(uri.clone())),
+            AssetFilter::Name(name) => leaf(query.text_match("name", name)),
+            AssetFilter::Symbol(symbol) => leaf(query.text_match("symbol", symbol)),
+        }
+    }
+
+    const fn uses_asset_data(&self) -> bool {
+        matches!(
+            self,
+            AssetFilter::JsonUri(_) | AssetFilter::Name(_) | AssetFilter::Symbol(_)
+        )
+    }
+}
+
+/// Matches the current creators of their asset, the ones the creators loaded along with it are
+/// narrowed down to: written at the asset's latest slot and, among those, at its latest `seq`,
+/// unless the asset was decompressed (`seq` 0).  An empty creator stands for an empty creator
+/// array.
+pub(crate) fn current_creator() -> SimpleExpr {
+    Expr::cust(
+        "octet_length(asset_creators.creator) > 0 \
+         AND asset_creators.slot_updated IS NOT DISTINCT FROM \
+         (SELECT MAX(ac.slot_updated) FROM asset_creators ac WHERE ac.asset_id = asset_creators.asset_id) \
+         AND asset_creators.seq IS NOT DISTINCT FROM \
+         (SELECT CASE WHEN bool_or(ac.seq = 0) THEN 0 ELSE MAX(ac.seq) END FROM asset_creators ac \
+         WHERE ac.asset_id = asset_creators.asset_id \
+         AND ac.slot_updated IS NOT DISTINCT FROM asset_creators.slot_updated)",
+    )
+}
+
+/// Matches assets with any of `creators` (or any creator at all when `None`), optionally only
+/// counting the creators of the given verification status.  A subquery rather than a join, so an
+/// asset matching several creators is only returned once.  Only current creators count.
+fn creator_assets(creators: Option<Vec<Vec<u8>>>, verified: Option<bool>) -> SimpleExpr {
+    let mut creator_assets = Query::select()
+        .column(asset_creators::Column::AssetId)
+        .from(asset_creators::Entity)
+        .and_where(current_creator())
+        .to_owned();
+    if let Some(creators) = creators {
+        creator_assets.and_where(asset_creators::Column::Creator.is_in(creators));
+    }
+    if let Some(verified) = verified {
+        creator_assets.and_where(asset_creators::Column::Verified.eq(verified));
+    }
+    asset::Column::Id.in_subquery(creator_assets)
+}
+
+fn authority_assets(authorities: Vec<Vec<u8>>) -> SimpleExpr {
+    asset::Column::Id.in_subquery(
+        Query::select()
+            .column(asset_authority::Column::AssetId)
+            .from(asset_authority::Entity)
+            .and_where(asset_authority::Column::Authority.is_in(authorities))
+            .to_owned(),
+    )
+}
+
+/// Matches assets in any of the `(group_key, group_value)` groups.
+fn grouping_assets(groupings: Vec<(String, String)>) -> SimpleExpr {
+    let groups = groupings
+        .into_iter()
+        .fold(Condition::any(), |groups, (key, value)| {
+            groups.add(
+                Condition::all()
+                    .add(asset_grouping::Column::GroupKey.eq(key))
+                    .add(asset_grouping::Column::GroupValue.eq(value)),
+            )
+        });
+    asset::Column::Id.in_subquery(
+        Query::select()
+            .column(asset_grouping::Column::AssetId)
+            .from(asset_grouping::Entity)
+            .cond_where(groups)
+            .to_owned(),
+    )
 }
 
 impl SearchAssetsQuery {
@@ -94,9 +458,9 @@ impl SearchAssetsQuery {
                     .map(|x| asset::Column::SpecificationAssetClass.eq(x)),
             )
             .add_option(
-                self.owner_address
+                self.owner_addresses
                     .to_owned()
-                    .map(|x| asset::Column::Owner.eq(x)),
+                    .map(|x| scopes::asset::owner_condition(x, self.show_fungible)),
             )
             .add_option(
                 self.delegate
@@ -129,7 +493,7 @@ impl SearchAssetsQuery {
 
         if let Some(s) = self.supply {
             conditions = conditions.add(asset::Column::Supply.eq(s));
-        } else {
+        } else if !self.filter_has(|node| matches!(node, AssetFilter::Supply(_))) {
             // By default, we ignore malformed tokens by ignoring tokens with supply=0
             // unless they are burnt.
             //
@@ -144,7 +508,9 @@ impl SearchAssetsQuery {
 
         if let Some(o) = self.owner_type.clone() {
             conditions = conditions.add(asset::Column::OwnerType.eq(o));
-        } else {
+        } else if !self.show_fungible
+            && !self.filter_has(|node| matches!(node, AssetFilter::OwnerType(_)))
+        {
             // Default to NFTs
             //
             // In theory, the owner_type=single check should be sufficient,
@@ -157,84 +523,63 @@ impl SearchAssetsQuery {
             ));
         }
 
-        if let Some(c) = self.creator_address.to_owned() {
-            conditions = conditions.add(asset_creators::Column::Creator.eq(c));
+        // The multi-valued filters match through subqueries rather than joins, so an asset
+        // matching several of the values is only returned once.
+        let mut joins = Vec::new();
+        if let Some(creators) = self.creator_addresses.to_owned() {
+            conditions = conditions.add(creator_assets(Some(creators), self.creator_verified));
+        } else if let Some(cv) = self.creator_verified {
+            // Without specifying the creators themselves, there is no index being hit.
+            // So in some rare scenarios, this query could be very slow.
+            conditions = conditions.add(creator_assets(None, Some(cv)));
         }
 
-        // Without specifying the creators themselves, there is no index being hit.
-        // So in some rare scenarios, this query could be very slow.
-        if let Some(cv) = self.creator_verified.to_owned() {
-            conditions = conditions.add(asset_creators::Column::Verified.eq(cv));
+        if let Some(authorities) = self.authority_addresses.to_owned() {
+            conditions = conditions.add(authority_assets(authorities));
         }
 
-        // If creator_address or creator_verified is set, join with asset_creators
-        let mut joins = Vec::new();
-        if self.creator_address.is_some() || self.creator_verified.is_some() {
-            let rel = extensions::asset_creators::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_creators::Column::AssetId)
-                        .eq(Expr::tbl(left, asset::Column::Id))
-                        .into_condition()
-                });
-            joins.push(rel);
+        if let Some(groupings) = self.groupings.to_owned() {
+            conditions = conditions.add(grouping_assets(groupings));
         }
 
-        if let Some(a) = self.authority_address.to_owned() {
-            conditions = conditions.add(asset_authority::Column::Authority.eq(a));
-            let rel = extensions::asset_authority::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_authority::Column::AssetId)
-                        .eq(Expr::tbl(left, asset::Column::Id))
-                        .into_condition()
-                });
-            joins.push(rel);
+        if let Some(ju) = self.json_uri.to_owned() {
+            conditions = conditions.add(asset_data::Column::MetadataUrl.eq(ju));
         }
 
-        if let Some(g) = self.grouping.to_owned() {
-            let cond = Condition::all()
-                .add(asset_grouping::Column::GroupKey.eq(g.0))
-                .add(asset_grouping::Column::GroupValue.eq(g.1));
-            conditions = conditions.add(cond);
-            let rel = extensions::asset_grouping::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_grouping::Column::AssetId)
-                        .eq(Expr::tbl(left, asset::Column::Id))
-                        .into_condition()
-                });
-            joins.push(rel);
+        if let Some(n) = self.name.as_deref() {
+            conditions = conditions.add(self.text_match("name", n));
         }
 
-        if let Some(ju) = self.json_uri.to_owned() {
-            let cond = Condition::all().add(asset_data::Column::MetadataUrl.eq(ju));
-            conditions = conditions.add(cond);
-            let rel = extensions::asset_data::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_data::Column::Id)
-                        .eq(Expr::tbl(left, asset::Column::AssetData))
-                        .into_condition()
-                });
-            joins.push(rel);
+        if let Some(s) = self.symbol.as_deref() {
+            conditions = conditions.add(self.text_match("symbol", s));
         }
 
-        if let Some(n) = self.name.to_owned() {
-            let name_as_str = std::str::from_utf8(&n).map_err(|_| {
-                DbErr::Custom(
-                    "Could not convert raw name bytes into string for comparison".to_owned(),
-                )
-            })?;
-
-            let name_expr =
-                SimpleExpr::Custom(format!("chain_data->>'name' LIKE '%{}%'", name_as_str));
+        if let Some(attributes) = self.attributes.to_owned() {
+            let mut cond = match self.attributes_condition_type {
+                None | Some(ConditionType::All) => Condition::all(),
+                Some(ConditionType::Any) => Condition::any(),
+            };
+            for (trait_type, value) in attributes {
+                cond = cond.add(
+                    asset::Column::AssetData.in_subquery(
+                        Query::select()
+                            .column(asset_attributes::Column::AssetDataId)
+                            .from(asset_attributes::Entity)
+                            .and_where(asset_attributes::Column::TraitType.eq(trait_type))
+                            .and_where(asset_attributes::Column::Value.eq(value))
+                            .to_owned(),
+                    ),
+                );
+            }
+            conditions = conditions.add(cond);
+        }
 
-            conditions = conditions.add(name_expr);
+        // If json_uri, name or symbol is set, join with asset_data
+        if self.json_uri.is_some()
+            || self.name.is_some()
+            || self.symbol.is_some()
+            || self.filter_has(AssetFilter::uses_asset_data)
+        {
             let rel = extensions::asset_data::Relation::Asset
                 .def()
                 .rev()
@@ -246,12 +591,55 @@ impl SearchAssetsQuery {
             joins.push(rel);
         }
 
+        let conditions = match self.negate {
+            None | Some(false) => conditions,
+            Some(true) => conditions.not(),
+        };
         Ok((
-            match self.negate {
-                None | Some(false) => conditions,
-                Some(true) => conditions.not(),
+            match &self.filter {
+                Some(filter) => Condition::all().add(conditions).add(filter.condition(self)),
+                None => conditions,
             },
             joins,
         ))
     }
+
+    fn filter_has(&self, predicate: impl Fn(&AssetFilter) -> bool) -> bool {
+        self.filter
+            .as_ref()
+            .is_some_and(|filter| filter.any_node(&predicate))
+    }
+
+    /// Matches a text field of the asset metadata against `value`. The value is always bound as
+    /// a parameter, with `LIKE` wildcards escaped, so it is compared literally.
+    fn text_match(&self, field: &str, value: &str) -> SimpleExpr {
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let pattern = match self.text_match_mode.unwrap_or_default() {
+            TextMatchMode::Exact => escaped,
+            TextMatchMode::Prefix => format!("{}%", escaped),
+            TextMatchMode::Contains => format!("%{}%", escaped),
+        };
+        let operator = if self.case_insensitive.unwrap_or(false) {
+            "ILIKE"
+        } else {
+            "LIKE"
+        };
+
+        // Matches on the sanitized copy in `chain_data` rather than `raw_name` or `raw_symbol`:
+        // those are NUL-padded bytes that aren't guaranteed to be valid UTF-8, so they can't be
+        // pattern matched as text. The trigram indexes are built on exactly these expressions,
+        // so the planner can use them. Backslash is the default `LIKE` escape character in
+        // Postgres. Metadata without the field doesn't match rather than matching NULL, which a
+        // filter could negate.
+        Expr::cust_with_values(
+            &format!(
+                "(asset_data.chain_data->>'{0}' IS NOT NULL AND asset_data.chain_data->>'{0}' {1} $1)",
+                field, operator
+            ),
+            [pattern],
+        )
+    }
 }
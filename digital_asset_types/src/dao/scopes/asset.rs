@@ -1,23 +1,34 @@
 use crate::{
     dao::{
         asset::{self},
-        asset_authority, asset_creators, asset_data, asset_grouping, cl_audits_v2,
-        extensions::{self, instruction::PascalCase},
+        asset_authority, asset_creators, asset_data, asset_events, asset_grouping,
+        asset_signatures, cl_audits_v2, current_creator, extensions,
         sea_orm_active_enums::Instruction,
-        Cursor, FullAsset, GroupingSize, Pagination,
+        token_accounts, tokens, AssetSignatures, Cursor, FullAsset, GroupingSize, HolderCount,
+        Pagination, SortColumn, SortValue,
+    },
+    rpc::{
+        filter::AssetSortDirection,
+        options::{AssetField, Options},
     },
-    rpc::filter::AssetSortDirection,
 };
 use indexmap::IndexMap;
-use sea_orm::{entity::*, query::*, ConnectionTrait, DbErr, Order};
+use sea_orm::{
+    entity::*,
+    query::*,
+    sea_query::{Expr, Query, SimpleExpr},
+    ConnectionTrait, DbErr, Order,
+};
 use std::collections::HashMap;
 
+/// Pages through `stmt`, which must be ordered by `sort_expr` (if any) and then by `column`.
 pub fn paginate<T, C>(
     pagination: &Pagination,
     limit: u64,
     stmt: T,
     sort_direction: Order,
     column: C,
+    sort_expr: Option<SimpleExpr>,
 ) -> T
 where
     T: QueryFilter + QuerySelect,
@@ -39,7 +50,15 @@ where
             }
         }
         Pagination::Cursor(cursor) => {
-            if *cursor != Cursor::default() {
+            if let (Some(sort_expr), Some(sort_value)) = (sort_expr, cursor.sort_value.clone()) {
+                stmt = stmt.filter(after_sort_value(
+                    sort_expr,
+                    sort_value,
+                    column,
+                    cursor.id.clone(),
+                    sort_direction,
+                ));
+            } else if *cursor != Cursor::default() {
                 if sort_direction == sea_orm::Order::Asc {
                     stmt = stmt.filter(column.gt(cursor.id.clone()));
                 } else {
@@ -51,37 +70,95 @@ where
     stmt.limit(limit)
 }
 
+/// Matches the rows ordered after `(sort_value, id)` when ordering by `sort_expr` and then by
+/// `column`. Postgres orders NULLs last when ascending and first when descending.
+fn after_sort_value<C: ColumnTrait>(
+    sort_expr: SimpleExpr,
+    sort_value: SortValue,
+    column: C,
+    id: Option<Vec<u8>>,
+    sort_direction: Order,
+) -> Condition {
+    let asc = sort_direction == Order::Asc;
+    let after_id = if asc { column.gt(id) } else { column.lt(id) };
+    let sort_column = || Expr::expr(sort_expr.clone());
+    match sort_value.into_value() {
+        Some(v) => {
+            let cond = Condition::any()
+                .add(if asc {
+                    sort_column().gt(v.clone())
+                } else {
+                    sort_column().lt(v.clone())
+                })
+                .add(sort_column().eq(v).and(after_id));
+            if asc {
+                cond.add(sort_column().is_null())
+            } else {
+                cond
+            }
+        }
+        None => {
+            let cond = Condition::any().add(sort_column().is_null().and(after_id));
+            if asc {
+                cond
+            } else {
+                cond.add(sort_column().is_not_null())
+            }
+        }
+    }
+}
+
+/// Assets with any of `creators` among their current creators.
 #[allow(clippy::too_many_arguments)]
 pub async fn get_by_creator(
     conn: &impl ConnectionTrait,
-    creator: Vec<u8>,
+    creators: Vec<Vec<u8>>,
     only_verified: bool,
-    sort_by: Option<asset::Column>,
+    sort_by: Option<SortColumn>,
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let mut condition = Condition::all()
-        .add(asset_creators::Column::Creator.eq(creator.clone()))
-        .add(asset::Column::Supply.gt(0));
-    if only_verified {
-        condition = condition.add(asset_creators::Column::Verified.eq(true));
-    }
-    get_by_related_condition(
+    get_assets_by_condition(
         conn,
-        condition,
-        extensions::asset::Relation::AssetCreators,
+        creator_condition(creators, only_verified),
+        vec![],
         sort_by,
         sort_direction,
         pagination,
         limit,
-        show_unverified_collections,
-        Some(creator),
+        options,
     )
     .await
 }
 
+pub async fn count_by_creator(
+    conn: &impl ConnectionTrait,
+    creators: Vec<Vec<u8>>,
+    only_verified: bool,
+) -> Result<u64, DbErr> {
+    count_assets_by_condition(conn, creator_condition(creators, only_verified), vec![]).await
+}
+
+/// Matches assets through a subquery rather than a join, so an asset with several of the
+/// creators is only returned once. Only the current creators of an asset count, like the creators
+/// loaded along with it.
+fn creator_condition(creators: Vec<Vec<u8>>, only_verified: bool) -> Condition {
+    let mut creator_assets = Query::select()
+        .column(asset_creators::Column::AssetId)
+        .from(asset_creators::Entity)
+        .and_where(asset_creators::Column::Creator.is_in(creators))
+        .and_where(current_creator())
+        .to_owned();
+    if only_verified {
+        creator_assets.and_where(asset_creators::Column::Verified.eq(true));
+    }
+    Condition::all()
+        .add(asset::Column::Id.in_subquery(creator_assets))
+        .add(asset::Column::Supply.gt(0))
+}
+
 pub async fn get_grouping(
     conn: &impl ConnectionTrait,
     group_key: String,
@@ -90,8 +167,8 @@ pub async fn get_grouping(
     let size = asset_grouping::Entity::find()
         .filter(
             Condition::all()
-                .add(asset_grouping::Column::GroupKey.eq(group_key))
-                .add(asset_grouping::Column::GroupValue.eq(group_value))
+                .add(asset_grouping::Column::GroupKey.eq(group_key.clone()))
+                .add(asset_grouping::Column::GroupValue.eq(group_value.clone()))
                 .add(
                     Condition::any()
                         .add(asset_grouping::Column::Verified.eq(true))
@@ -100,7 +177,57 @@ pub async fn get_grouping(
         )
         .count(conn)
         .await?;
-    Ok(GroupingSize { size })
+    let name = match bs58::decode(&group_value).into_vec() {
+        Ok(collection) if group_key == "collection" => asset_data::Entity::find_by_id(collection)
+            .one(conn)
+            .await?
+            .and_then(|data| data.chain_data.get("name")?.as_str().map(str::to_string)),
+        _ => None,
+    };
+    Ok(GroupingSize { size, name })
+}
+
+/// Distinct owners of the assets in a verified collection, with how many assets each owns,
+/// ordered by owner.
+pub async fn get_collection_holders(
+    conn: &impl ConnectionTrait,
+    collection: String,
+    pagination: &Pagination,
+    limit: u64,
+) -> Result<Vec<HolderCount>, DbErr> {
+    let stmt = asset::Entity::find()
+        .select_only()
+        .column(asset::Column::Owner)
+        .column_as(asset::Column::Id.count(), "count")
+        .join(
+            JoinType::InnerJoin,
+            extensions::asset::Relation::AssetGrouping.def(),
+        )
+        .filter(
+            Condition::all()
+                .add(asset_grouping::Column::GroupKey.eq("collection"))
+                .add(asset_grouping::Column::GroupValue.eq(collection))
+                .add(
+                    Condition::any()
+                        .add(asset_grouping::Column::Verified.eq(true))
+                        .add(asset_grouping::Column::Verified.is_null()),
+                )
+                .add(asset::Column::Burnt.eq(false))
+                .add(asset::Column::Owner.is_not_null()),
+        )
+        .group_by(asset::Column::Owner)
+        .order_by(asset::Column::Owner, Order::Asc);
+    paginate(
+        pagination,
+        limit,
+        stmt,
+        Order::Asc,
+        asset::Column::Owner,
+        None,
+    )
+    .into_model::<HolderCount>()
+    .all(conn)
+    .await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -108,17 +235,45 @@ pub async fn get_by_grouping(
     conn: &impl ConnectionTrait,
     group_key: String,
     group_value: String,
-    sort_by: Option<asset::Column>,
+    sort_by: Option<SortColumn>,
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
+    get_by_related_condition(
+        conn,
+        grouping_condition(group_key, group_value, options),
+        extensions::asset::Relation::AssetGrouping,
+        sort_by,
+        sort_direction,
+        pagination,
+        limit,
+        options,
+    )
+    .await
+}
+
+pub async fn count_by_grouping(
+    conn: &impl ConnectionTrait,
+    group_key: String,
+    group_value: String,
+    options: &Options,
+) -> Result<u64, DbErr> {
+    count_by_related_condition(
+        conn,
+        grouping_condition(group_key, group_value, options),
+        extensions::asset::Relation::AssetGrouping,
+    )
+    .await
+}
+
+fn grouping_condition(group_key: String, group_value: String, options: &Options) -> Condition {
     let mut condition = asset_grouping::Column::GroupKey
         .eq(group_key)
         .and(asset_grouping::Column::GroupValue.eq(group_value));
 
-    if !show_unverified_collections {
+    if !options.show_unverified_collections {
         condition = condition.and(
             asset_grouping::Column::Verified
                 .eq(true)
@@ -126,52 +281,175 @@ pub async fn get_by_grouping(
         );
     }
 
-    get_by_related_condition(
+    Condition::all()
+        .add(condition)
+        .add(asset::Column::Supply.gt(0))
+}
+
+/// Assets held by any of `owners`.
+pub async fn get_assets_by_owner(
+    conn: &impl ConnectionTrait,
+    owners: Vec<Vec<u8>>,
+    sort_by: Option<SortColumn>,
+    sort_direction: Order,
+    pagination: &Pagination,
+    limit: u64,
+    options: &Options,
+) -> Result<Vec<FullAsset>, DbErr> {
+    let mut assets = get_assets_by_condition(
         conn,
-        Condition::all()
-            .add(condition)
-            .add(asset::Column::Supply.gt(0)),
-        extensions::asset::Relation::AssetGrouping,
+        owned_assets_condition(owners.clone(), options),
+        vec![],
         sort_by,
         sort_direction,
         pagination,
         limit,
-        show_unverified_collections,
-        None,
+        options,
     )
-    .await
+    .await?;
+    if options.show_fungible {
+        add_owner_token_balances(conn, owners, &mut assets).await?;
+    }
+    Ok(assets)
 }
 
-pub async fn get_assets_by_owner(
+pub async fn count_assets_by_owner(
     conn: &impl ConnectionTrait,
-    owner: Vec<u8>,
-    sort_by: Option<asset::Column>,
+    owners: Vec<Vec<u8>>,
+    options: &Options,
+) -> Result<u64, DbErr> {
+    count_assets_by_condition(conn, owned_assets_condition(owners, options), vec![]).await
+}
+
+fn owned_assets_condition(owners: Vec<Vec<u8>>, options: &Options) -> Condition {
+    Condition::all()
+        .add(owner_condition(owners, options.show_fungible))
+        .add(asset::Column::Supply.gt(0))
+}
+
+pub async fn get_assets_by_delegate(
+    conn: &impl ConnectionTrait,
+    delegate: Vec<u8>,
+    sort_by: Option<SortColumn>,
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let cond = Condition::all()
-        .add(asset::Column::Owner.eq(owner))
-        .add(asset::Column::Supply.gt(0));
     get_assets_by_condition(
         conn,
-        cond,
+        delegated_assets_condition(delegate),
         vec![],
         sort_by,
         sort_direction,
         pagination,
         limit,
-        show_unverified_collections,
+        options,
     )
     .await
 }
 
+pub async fn count_assets_by_delegate(
+    conn: &impl ConnectionTrait,
+    delegate: Vec<u8>,
+) -> Result<u64, DbErr> {
+    count_assets_by_condition(conn, delegated_assets_condition(delegate), vec![]).await
+}
+
+fn delegated_assets_condition(delegate: Vec<u8>) -> Condition {
+    Condition::all()
+        .add(asset::Column::Delegate.eq(delegate))
+        .add(asset::Column::Supply.gt(0))
+}
+
+/// Matches assets owned by `owner`. Fungible assets never have a single owner, so when they are
+/// requested they are matched through the owner's non-empty token accounts instead.
+pub fn owner_condition(owners: Vec<Vec<u8>>, show_fungible: bool) -> Condition {
+    // Assets without an owner don't match rather than matching NULL, so the condition can be
+    // negated.
+    let owned = Condition::all()
+        .add(asset::Column::Owner.is_not_null())
+        .add(asset::Column::Owner.is_in(owners.clone()));
+    if show_fungible {
+        Condition::any().add(owned).add(
+            asset::Column::Id.in_subquery(
+                Query::select()
+                    .column(token_accounts::Column::Mint)
+                    .from(token_accounts::Entity)
+                    .and_where(token_accounts::Column::Owner.is_in(owners))
+                    .and_where(token_accounts::Column::Amount.gt(0))
+                    .to_owned(),
+            ),
+        )
+    } else {
+        owned
+    }
+}
+
+/// Sets the balance `owners` hold of each asset, summed over all of their token accounts for
+/// that mint.
+pub async fn add_owner_token_balances(
+    conn: &impl ConnectionTrait,
+    owners: Vec<Vec<u8>>,
+    assets: &mut [FullAsset],
+) -> Result<(), DbErr> {
+    let mints = assets
+        .iter()
+        .map(|a| a.asset.id.clone())
+        .collect::<Vec<_>>();
+    let token_accounts = token_accounts::Entity::find()
+        .filter(token_accounts::Column::Owner.is_in(owners))
+        .filter(token_accounts::Column::Mint.is_in(mints))
+        .all(conn)
+        .await?;
+
+    let balances =
+        token_accounts
+            .into_iter()
+            .fold(HashMap::new(), |mut acc: HashMap<Vec<u8>, i64>, ta| {
+                *acc.entry(ta.mint).or_default() += ta.amount;
+                acc
+            });
+    for asset in assets.iter_mut() {
+        asset.token_balance = Some(balances.get(&asset.asset.id).copied().unwrap_or(0));
+    }
+    Ok(())
+}
+
+/// Leaves of `tree` from `from_leaf` to `to_leaf` (both inclusive), ordered by leaf index.
+pub async fn get_assets_by_tree(
+    conn: &impl ConnectionTrait,
+    tree: Vec<u8>,
+    from_leaf: Option<i64>,
+    to_leaf: Option<i64>,
+    pagination: &Pagination,
+    limit: u64,
+    options: &Options,
+) -> Result<Vec<FullAsset>, DbErr> {
+    let mut condition = Condition::all()
+        .add(asset::Column::TreeId.eq(tree))
+        .add(asset::Column::Supply.gt(0));
+    if let Some(from_leaf) = from_leaf {
+        condition = condition.add(asset::Column::Nonce.gte(from_leaf));
+    }
+    if let Some(to_leaf) = to_leaf {
+        condition = condition.add(asset::Column::Nonce.lte(to_leaf));
+    }
+    let stmt = asset::Entity::find()
+        .filter(condition)
+        .order_by(asset::Column::Nonce, Order::Asc);
+    let assets = paginate_by_int(pagination, limit, stmt, Order::Asc, asset::Column::Nonce)?
+        .all(conn)
+        .await?;
+    get_related_for_assets(conn, assets, options).await
+}
+
 pub async fn get_assets(
     conn: &impl ConnectionTrait,
     asset_ids: Vec<Vec<u8>>,
     pagination: &Pagination,
     limit: u64,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
     let cond = Condition::all()
         .add(asset::Column::Id.is_in(asset_ids))
@@ -185,7 +463,10 @@ pub async fn get_assets(
         Order::Asc,
         pagination,
         limit,
-        false,
+        &Options {
+            show_unverified_collections: false,
+            ..options.clone()
+        },
     )
     .await
 }
@@ -193,40 +474,53 @@ pub async fn get_assets(
 pub async fn get_by_authority(
     conn: &impl ConnectionTrait,
     authority: Vec<u8>,
-    sort_by: Option<asset::Column>,
+    sort_by: Option<SortColumn>,
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let cond = Condition::all()
-        .add(asset_authority::Column::Authority.eq(authority))
-        .add(asset::Column::Supply.gt(0));
     get_by_related_condition(
         conn,
-        cond,
+        authority_condition(authority),
         extensions::asset::Relation::AssetAuthority,
         sort_by,
         sort_direction,
         pagination,
         limit,
-        show_unverified_collections,
-        None,
+        options,
     )
     .await
 }
 
+pub async fn count_by_authority(
+    conn: &impl ConnectionTrait,
+    authority: Vec<u8>,
+) -> Result<u64, DbErr> {
+    count_by_related_condition(
+        conn,
+        authority_condition(authority),
+        extensions::asset::Relation::AssetAuthority,
+    )
+    .await
+}
+
+fn authority_condition(authority: Vec<u8>) -> Condition {
+    Condition::all()
+        .add(asset_authority::Column::Authority.eq(authority))
+        .add(asset::Column::Supply.gt(0))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn get_by_related_condition<E>(
     conn: &impl ConnectionTrait,
     condition: Condition,
     relation: E,
-    sort_by: Option<asset::Column>,
+    sort_by: Option<SortColumn>,
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
-    required_creator: Option<Vec<u8>>,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr>
 where
     E: RelationTrait,
@@ -235,23 +529,45 @@ where
         .filter(condition)
         .join(JoinType::LeftJoin, relation.def());
 
+    // Cursors need a stable order, so fall back to the id when no sorting was requested.
+    let sort_by = match pagination {
+        Pagination::Cursor(_) => sort_by.or(Some(SortColumn::Id)),
+        _ => sort_by,
+    };
     if let Some(col) = sort_by {
         stmt = stmt
-            .order_by(col, sort_direction.clone())
+            .order_by(col.expr(), sort_direction.clone())
             .order_by(asset::Column::Id, sort_direction.clone());
     }
 
-    let assets = paginate(pagination, limit, stmt, sort_direction, asset::Column::Id)
-        .all(conn)
-        .await?;
-    get_related_for_assets(conn, assets, show_unverified_collections, required_creator).await
+    let assets = paginate(
+        pagination,
+        limit,
+        stmt,
+        sort_direction,
+        asset::Column::Id,
+        sort_by.map(SortColumn::expr),
+    )
+    .all(conn)
+    .await?;
+    get_related_for_assets(conn, assets, options).await
+}
+
+async fn count_by_related_condition<E>(
+    conn: &impl ConnectionTrait,
+    condition: Condition,
+    relation: E,
+) -> Result<u64, DbErr>
+where
+    E: RelationTrait,
+{
+    count_assets_by_condition(conn, condition, vec![relation.def()]).await
 }
 
 pub async fn get_related_for_assets(
     conn: &impl ConnectionTrait,
     assets: Vec<asset::Model>,
-    show_unverified_collections: bool,
-    required_creator: Option<Vec<u8>>,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
     let asset_ids = assets.iter().map(|a| a.id.clone()).collect::<Vec<_>>();
 
@@ -278,6 +594,8 @@ pub async fn get_related_for_assets(
                 authorities: vec![],
                 creators: vec![],
                 groups: vec![],
+                token_info: None,
+                token_balance: None,
             };
             acc.insert(id, fa);
         };
@@ -285,47 +603,43 @@ pub async fn get_related_for_assets(
     });
     let ids = assets_map.keys().cloned().collect::<Vec<_>>();
 
-    // Get all creators for all assets in `assets_map``.
-    let creators = asset_creators::Entity::find()
-        .filter(asset_creators::Column::AssetId.is_in(ids.clone()))
-        .order_by_asc(asset_creators::Column::AssetId)
-        .order_by_asc(asset_creators::Column::Position)
-        .all(conn)
-        .await?;
+    // Only the sections that were asked for are loaded.
+    if options.includes(AssetField::Creators) {
+        // Get all creators for all assets in `assets_map``.
+        let creators = asset_creators::Entity::find()
+            .filter(asset_creators::Column::AssetId.is_in(ids.clone()))
+            .order_by_asc(asset_creators::Column::AssetId)
+            .order_by_asc(asset_creators::Column::Position)
+            .all(conn)
+            .await?;
 
-    // Add the creators to the assets in `asset_map``.
-    for c in creators.into_iter() {
-        if let Some(asset) = assets_map.get_mut(&c.asset_id) {
-            asset.creators.push(c);
+        // Add the creators to the assets in `asset_map``.
+        for c in creators.into_iter() {
+            if let Some(asset) = assets_map.get_mut(&c.asset_id) {
+                asset.creators.push(c);
+            }
         }
-    }
 
-    // Filter out stale creators from each asset.
-    for (_id, asset) in assets_map.iter_mut() {
-        filter_out_stale_creators(&mut asset.creators);
-    }
-
-    // If we passed in a required creator, we make sure that creator is still in the creator array
-    // of each asset after stale creators were filtered out above.  Only retain those assets that
-    // have the required creator.  This corrects `getAssetByCreators` from returning assets for
-    // which the required creator is no longer in the creator array.
-    if let Some(required) = required_creator {
-        assets_map.retain(|_id, asset| asset.creators.iter().any(|c| c.creator == required));
+        // Filter out stale creators from each asset.
+        for (_id, asset) in assets_map.iter_mut() {
+            filter_out_stale_creators(&mut asset.creators);
+        }
     }
 
-    let ids = assets_map.keys().cloned().collect::<Vec<_>>();
-    let authorities = asset_authority::Entity::find()
-        .filter(asset_authority::Column::AssetId.is_in(ids.clone()))
-        .order_by_asc(asset_authority::Column::AssetId)
-        .all(conn)
-        .await?;
-    for a in authorities.into_iter() {
-        if let Some(asset) = assets_map.get_mut(&a.asset_id) {
-            asset.authorities.push(a);
+    if options.includes(AssetField::Authorities) {
+        let authorities = asset_authority::Entity::find()
+            .filter(asset_authority::Column::AssetId.is_in(ids.clone()))
+            .order_by_asc(asset_authority::Column::AssetId)
+            .all(conn)
+            .await?;
+        for a in authorities.into_iter() {
+            if let Some(asset) = assets_map.get_mut(&a.asset_id) {
+                asset.authorities.push(a);
+            }
         }
     }
 
-    let cond = if show_unverified_collections {
+    let cond = if options.show_unverified_collections {
         Condition::all()
     } else {
         Condition::any()
@@ -335,16 +649,30 @@ pub async fn get_related_for_assets(
             .add(asset_grouping::Column::Verified.is_null())
     };
 
-    let grouping = asset_grouping::Entity::find()
-        .filter(asset_grouping::Column::AssetId.is_in(ids.clone()))
-        .filter(asset_grouping::Column::GroupValue.is_not_null())
-        .filter(cond)
-        .order_by_asc(asset_grouping::Column::AssetId)
-        .all(conn)
-        .await?;
-    for g in grouping.into_iter() {
-        if let Some(asset) = assets_map.get_mut(&g.asset_id) {
-            asset.groups.push(g);
+    if options.includes(AssetField::Grouping) {
+        let grouping = asset_grouping::Entity::find()
+            .filter(asset_grouping::Column::AssetId.is_in(ids.clone()))
+            .filter(asset_grouping::Column::GroupValue.is_not_null())
+            .filter(cond)
+            .order_by_asc(asset_grouping::Column::AssetId)
+            .all(conn)
+            .await?;
+        for g in grouping.into_iter() {
+            if let Some(asset) = assets_map.get_mut(&g.asset_id) {
+                asset.groups.push(g);
+            }
+        }
+    }
+
+    if options.show_fungible && options.includes(AssetField::TokenInfo) {
+        let tokens = tokens::Entity::find()
+            .filter(tokens::Column::Mint.is_in(ids))
+            .all(conn)
+            .await?;
+        for t in tokens.into_iter() {
+            if let Some(asset) = assets_map.get_mut(&t.mint) {
+                asset.token_info = Some(t);
+            }
         }
     }
 
@@ -356,35 +684,65 @@ pub async fn get_assets_by_condition(
     conn: &impl ConnectionTrait,
     condition: Condition,
     joins: Vec<RelationDef>,
-    sort_by: Option<asset::Column>,
+    sort_by: Option<SortColumn>,
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
     let mut stmt = asset::Entity::find();
     for def in joins {
         stmt = stmt.join(JoinType::LeftJoin, def);
     }
     stmt = stmt.filter(condition);
+    // Cursors need a stable order, so fall back to the id when no sorting was requested.
+    let sort_by = match pagination {
+        Pagination::Cursor(_) => sort_by.or(Some(SortColumn::Id)),
+        _ => sort_by,
+    };
     if let Some(col) = sort_by {
         stmt = stmt
-            .order_by(col, sort_direction.clone())
+            .order_by(col.expr(), sort_direction.clone())
             .order_by(asset::Column::Id, sort_direction.clone());
     }
 
-    let assets = paginate(pagination, limit, stmt, sort_direction, asset::Column::Id)
-        .all(conn)
-        .await?;
-    let full_assets =
-        get_related_for_assets(conn, assets, show_unverified_collections, None).await?;
+    let assets = paginate(
+        pagination,
+        limit,
+        stmt,
+        sort_direction,
+        asset::Column::Id,
+        sort_by.map(SortColumn::expr),
+    )
+    .all(conn)
+    .await?;
+    let full_assets = get_related_for_assets(conn, assets, options).await?;
     Ok(full_assets)
 }
 
+/// Counts the distinct assets matching `condition`, ignoring pagination.
+pub async fn count_assets_by_condition(
+    conn: &impl ConnectionTrait,
+    condition: Condition,
+    joins: Vec<RelationDef>,
+) -> Result<u64, DbErr> {
+    let mut stmt = asset::Entity::find();
+    for def in joins {
+        stmt = stmt.join(JoinType::LeftJoin, def);
+    }
+    stmt.filter(condition)
+        .select_only()
+        .column(asset::Column::Id)
+        .distinct()
+        .count(conn)
+        .await
+}
+
 pub async fn get_by_id(
     conn: &impl ConnectionTrait,
     asset_id: Vec<u8>,
     include_no_supply: bool,
+    options: &Options,
 ) -> Result<FullAsset, DbErr> {
     let mut asset_data =
         asset::Entity::find_by_id(asset_id.clone()).find_also_related(asset_data::Entity);
@@ -398,38 +756,59 @@ pub async fn get_by_id(
         })?;
 
     let (asset, data) = asset_data;
-    let authorities: Vec<asset_authority::Model> = asset_authority::Entity::find()
-        .filter(asset_authority::Column::AssetId.eq(asset.id.clone()))
-        .order_by_asc(asset_authority::Column::AssetId)
-        .all(conn)
-        .await?;
-    let mut creators: Vec<asset_creators::Model> = asset_creators::Entity::find()
-        .filter(asset_creators::Column::AssetId.eq(asset.id.clone()))
-        .order_by_asc(asset_creators::Column::Position)
-        .all(conn)
-        .await?;
+    let authorities: Vec<asset_authority::Model> = if options.includes(AssetField::Authorities) {
+        asset_authority::Entity::find()
+            .filter(asset_authority::Column::AssetId.eq(asset.id.clone()))
+            .order_by_asc(asset_authority::Column::AssetId)
+            .all(conn)
+            .await?
+    } else {
+        vec![]
+    };
+    let mut creators: Vec<asset_creators::Model> = if options.includes(AssetField::Creators) {
+        asset_creators::Entity::find()
+            .filter(asset_creators::Column::AssetId.eq(asset.id.clone()))
+            .order_by_asc(asset_creators::Column::Position)
+            .all(conn)
+            .await?
+    } else {
+        vec![]
+    };
 
     filter_out_stale_creators(&mut creators);
 
-    let grouping: Vec<asset_grouping::Model> = asset_grouping::Entity::find()
-        .filter(asset_grouping::Column::AssetId.eq(asset.id.clone()))
-        .filter(asset_grouping::Column::GroupValue.is_not_null())
-        .filter(
-            Condition::any()
-                .add(asset_grouping::Column::Verified.eq(true))
-                // Older versions of the indexer did not have the verified flag. A group would be present if and only if it was verified.
-                // Therefore if verified is null, we can assume that the group is verified.
-                .add(asset_grouping::Column::Verified.is_null()),
-        )
-        .order_by_asc(asset_grouping::Column::AssetId)
-        .all(conn)
-        .await?;
+    let grouping: Vec<asset_grouping::Model> = if options.includes(AssetField::Grouping) {
+        asset_grouping::Entity::find()
+            .filter(asset_grouping::Column::AssetId.eq(asset.id.clone()))
+            .filter(asset_grouping::Column::GroupValue.is_not_null())
+            .filter(
+                Condition::any()
+                    .add(asset_grouping::Column::Verified.eq(true))
+                    // Older versions of the indexer did not have the verified flag. A group would be present if and only if it was verified.
+                    // Therefore if verified is null, we can assume that the group is verified.
+                    .add(asset_grouping::Column::Verified.is_null()),
+            )
+            .order_by_asc(asset_grouping::Column::AssetId)
+            .all(conn)
+            .await?
+    } else {
+        vec![]
+    };
+    let token_info = if options.show_fungible && options.includes(AssetField::TokenInfo) {
+        tokens::Entity::find_by_id(asset.id.clone())
+            .one(conn)
+            .await?
+    } else {
+        None
+    };
     Ok(FullAsset {
         asset,
         data,
         authorities,
         creators,
         groups: grouping,
+        token_info,
+        token_balance: None,
     })
 }
 
@@ -437,10 +816,11 @@ pub async fn fetch_transactions(
     conn: &impl ConnectionTrait,
     tree: Vec<u8>,
     leaf_idx: i64,
+    instruction: Option<Instruction>,
     pagination: &Pagination,
     limit: u64,
     sort_direction: Option<AssetSortDirection>,
-) -> Result<Vec<(String, String)>, DbErr> {
+) -> Result<Vec<cl_audits_v2::Model>, DbErr> {
     // Default sort direction is Desc
     // Similar to GetSignaturesForAddress in the Solana API
     let sort_direction = sort_direction.unwrap_or(AssetSortDirection::Desc);
@@ -451,42 +831,166 @@ pub async fn fetch_transactions(
 
     let mut stmt = cl_audits_v2::Entity::find().filter(cl_audits_v2::Column::Tree.eq(tree));
     stmt = stmt.filter(cl_audits_v2::Column::LeafIdx.eq(leaf_idx));
+    if let Some(instruction) = instruction {
+        stmt = stmt.filter(cl_audits_v2::Column::Instruction.eq(instruction));
+    }
     stmt = stmt.order_by(cl_audits_v2::Column::Seq, sort_order.clone());
 
-    stmt = paginate(
+    stmt = paginate_by_int(
         pagination,
         limit,
         stmt,
         sort_order,
         cl_audits_v2::Column::Seq,
-    );
-    let transactions = stmt.all(conn).await?;
-    let transaction_list = transactions
-        .into_iter()
-        .map(|transaction| {
-            let tx = bs58::encode(transaction.tx).into_string();
-            let ix = Instruction::to_pascal_case(&transaction.instruction).to_string();
-            (tx, ix)
-        })
-        .collect();
+    )?;
+    stmt.all(conn).await
+}
 
-    Ok(transaction_list)
+/// Pages through `stmt`, which must be ordered by the integer `column`, such as a sequence number
+/// or a leaf index. Cursors hold the value of `column` for the last item handed out.
+fn paginate_by_int<T, C>(
+    pagination: &Pagination,
+    limit: u64,
+    stmt: T,
+    sort_order: Order,
+    column: C,
+) -> Result<T, DbErr>
+where
+    T: QueryFilter + QuerySelect,
+    C: ColumnTrait,
+{
+    let cursor = match pagination {
+        Pagination::Cursor(cursor) => cursor,
+        Pagination::Keyset { before, after } if before.is_some() || after.is_some() => {
+            return Err(DbErr::Custom(
+                "Only cursor and page pagination are supported".to_string(),
+            ));
+        }
+        _ => return Ok(paginate(pagination, limit, stmt, sort_order, column, None)),
+    };
+    let mut stmt = stmt;
+    if let Some(id) = &cursor.id {
+        let value = id
+            .as_slice()
+            .try_into()
+            .map(i64::from_be_bytes)
+            .map_err(|_| DbErr::Custom("Invalid cursor".to_string()))?;
+        stmt = if sort_order == Order::Asc {
+            stmt.filter(column.gt(value))
+        } else {
+            stmt.filter(column.lt(value))
+        };
+    }
+    Ok(stmt.limit(limit))
 }
 
+/// Events recorded for `asset_id` by the bubblegum instructions that changed it.
+pub async fn get_asset_events(
+    conn: &impl ConnectionTrait,
+    asset_id: Vec<u8>,
+    pagination: &Pagination,
+    limit: u64,
+    sort_direction: Option<AssetSortDirection>,
+) -> Result<Vec<asset_events::Model>, DbErr> {
+    let sort_order = match sort_direction.unwrap_or(AssetSortDirection::Desc) {
+        AssetSortDirection::Asc => Order::Asc,
+        AssetSortDirection::Desc => Order::Desc,
+    };
+    let stmt = asset_events::Entity::find()
+        .filter(asset_events::Column::AssetId.eq(asset_id))
+        .order_by(asset_events::Column::Seq, sort_order.clone());
+    let stmt = paginate_by_int(
+        pagination,
+        limit,
+        stmt,
+        sort_order,
+        asset_events::Column::Seq,
+    )?;
+    stmt.all(conn).await
+}
+
+/// Transactions of an uncompressed asset, ordered by slot. Cursors hold the slot and the row id
+/// of the last item handed out.
+pub async fn fetch_mint_transactions(
+    conn: &impl ConnectionTrait,
+    mint: Vec<u8>,
+    pagination: &Pagination,
+    limit: u64,
+    sort_direction: Option<AssetSortDirection>,
+) -> Result<Vec<asset_signatures::Model>, DbErr> {
+    let sort_order = match sort_direction.unwrap_or(AssetSortDirection::Desc) {
+        AssetSortDirection::Asc => sea_orm::Order::Asc,
+        AssetSortDirection::Desc => sea_orm::Order::Desc,
+    };
+
+    let stmt = asset_signatures::Entity::find()
+        .filter(asset_signatures::Column::AssetId.eq(mint))
+        .order_by(asset_signatures::Column::Slot, sort_order.clone())
+        .order_by(asset_signatures::Column::Id, sort_order.clone());
+
+    let stmt = match pagination {
+        Pagination::Cursor(cursor) => {
+            let mut stmt = stmt;
+            if let Some(id) = &cursor.id {
+                if id.len() != 16 {
+                    return Err(DbErr::Custom("Invalid signature cursor".to_string()));
+                }
+                let (slot, id) = id.split_at(8);
+                let slot = i64::from_be_bytes(slot.try_into().unwrap_or_default());
+                let id = i64::from_be_bytes(id.try_into().unwrap_or_default());
+                let op = if sort_order == sea_orm::Order::Asc {
+                    ">"
+                } else {
+                    "<"
+                };
+                stmt = stmt.filter(Expr::cust_with_values(
+                    &format!("(slot, id) {} ($1, $2)", op),
+                    [slot, id],
+                ));
+            }
+            stmt.limit(limit)
+        }
+        Pagination::Keyset { before, after } if before.is_some() || after.is_some() => {
+            return Err(DbErr::Custom(
+                "Only cursor and page pagination are supported".to_string(),
+            ));
+        }
+        _ => paginate(
+            pagination,
+            limit,
+            stmt,
+            sort_order,
+            asset_signatures::Column::Id,
+            None,
+        ),
+    };
+    stmt.all(conn).await
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn get_asset_signatures(
     conn: &impl ConnectionTrait,
     asset_id: Option<Vec<u8>>,
     tree_id: Option<Vec<u8>>,
     leaf_idx: Option<i64>,
+    instruction: Option<Instruction>,
     pagination: &Pagination,
     limit: u64,
     sort_direction: Option<AssetSortDirection>,
-) -> Result<Vec<(String, String)>, DbErr> {
+) -> Result<AssetSignatures, DbErr> {
     // if tree_id and leaf_idx are provided, use them directly to fetch transactions
     if let (Some(tree_id), Some(leaf_idx)) = (tree_id, leaf_idx) {
-        let transactions =
-            fetch_transactions(conn, tree_id, leaf_idx, pagination, limit, sort_direction).await?;
-        return Ok(transactions);
+        let transactions = fetch_transactions(
+            conn,
+            tree_id,
+            leaf_idx,
+            instruction,
+            pagination,
+            limit,
+            sort_direction,
+        )
+        .await?;
+        return Ok(AssetSignatures::Compressed(transactions));
     }
 
     if asset_id.is_none() {
@@ -504,6 +1008,15 @@ pub async fn get_asset_signatures(
         .limit(1);
     let asset = stmt.one(conn).await?;
     if let Some(asset) = asset {
+        // Uncompressed assets are tracked by mint and have no bubblegum instructions.
+        if !asset.compressed {
+            if instruction.is_some() {
+                return Ok(AssetSignatures::Uncompressed(Vec::new()));
+            }
+            let transactions =
+                fetch_mint_transactions(conn, asset.id, pagination, limit, sort_direction).await?;
+            return Ok(AssetSignatures::Uncompressed(transactions));
+        }
         let tree = asset
             .tree_id
             .ok_or(DbErr::RecordNotFound("Tree not found".to_string()))?;
@@ -513,14 +1026,50 @@ pub async fn get_asset_signatures(
         let leaf_idx = asset
             .nonce
             .ok_or(DbErr::RecordNotFound("Leaf ID does not exist".to_string()))?;
-        let transactions =
-            fetch_transactions(conn, tree, leaf_idx, pagination, limit, sort_direction).await?;
-        Ok(transactions)
+        let transactions = fetch_transactions(
+            conn,
+            tree,
+            leaf_idx,
+            instruction,
+            pagination,
+            limit,
+            sort_direction,
+        )
+        .await?;
+        Ok(AssetSignatures::Compressed(transactions))
     } else {
-        Ok(Vec::new())
+        Ok(AssetSignatures::Compressed(Vec::new()))
     }
 }
 
+pub async fn get_token_accounts(
+    conn: &impl ConnectionTrait,
+    owner: Option<Vec<u8>>,
+    mint: Option<Vec<u8>>,
+    pagination: &Pagination,
+    limit: u64,
+) -> Result<Vec<token_accounts::Model>, DbErr> {
+    let condition = Condition::all()
+        .add_option(owner.map(|o| token_accounts::Column::Owner.eq(o)))
+        .add_option(mint.map(|m| token_accounts::Column::Mint.eq(m)));
+
+    let stmt = token_accounts::Entity::find()
+        .filter(condition)
+        .order_by(token_accounts::Column::Pubkey, Order::Asc);
+
+    paginate(
+        pagination,
+        limit,
+        stmt,
+        Order::Asc,
+        token_accounts::Column::Pubkey,
+        None,
+    )
+    .all(conn)
+    .await
+}
+
+// Kept in line with `current_creator`, which filters creators the same way in queries.
 fn filter_out_stale_creators(creators: &mut Vec<asset_creators::Model>) {
     // If the first creator is an empty Vec, it means the creator array is empty (which is allowed
     // for compressed assets in Bubblegum).
@@ -0,0 +1,3 @@
+/// Ingester progress and failed messages not updated for this many seconds are left out of the
+/// context slot, since their consumer has gone away or their stream has gone quiet.
+pub const STALE_PROGRESS_SECONDS: u64 = 600;
@@ -19,18 +19,27 @@ pub async fn get_assets_by_authority(
     let (sort_direction, sort_column) = create_sorting(sorting);
     let assets = scopes::asset::get_by_authority(
         db,
-        authority,
+        authority.clone(),
         sort_column,
         sort_direction,
         &pagination,
         page_options.limit,
-        options.show_unverified_collections,
+        options,
     )
     .await?;
-    Ok(build_asset_response(
-        assets,
-        page_options.limit,
-        &pagination,
-        options,
-    ))
+    let grand_total = if options.show_grand_total {
+        Some(scopes::asset::count_by_authority(db, authority).await?)
+    } else {
+        None
+    };
+    Ok(AssetList {
+        grand_total,
+        ..build_asset_response(
+            assets,
+            page_options.limit,
+            &pagination,
+            sort_column,
+            options,
+        )
+    })
 }
@@ -11,7 +11,7 @@ use super::common::{build_asset_response, create_pagination, create_sorting};
 #[allow(clippy::too_many_arguments)]
 pub async fn get_assets_by_creator(
     db: &DatabaseConnection,
-    creator: Vec<u8>,
+    creators: Vec<Vec<u8>>,
     only_verified: bool,
     sorting: AssetSorting,
     page_options: &PageOptions,
@@ -21,19 +21,28 @@ pub async fn get_assets_by_creator(
     let (sort_direction, sort_column) = create_sorting(sorting);
     let assets = scopes::asset::get_by_creator(
         db,
-        creator,
+        creators.clone(),
         only_verified,
         sort_column,
         sort_direction,
         &pagination,
         page_options.limit,
-        options.show_unverified_collections,
+        options,
     )
     .await?;
-    Ok(build_asset_response(
-        assets,
-        page_options.limit,
-        &pagination,
-        options,
-    ))
+    let grand_total = if options.show_grand_total {
+        Some(scopes::asset::count_by_creator(db, creators, only_verified).await?)
+    } else {
+        None
+    };
+    Ok(AssetList {
+        grand_total,
+        ..build_asset_response(
+            assets,
+            page_options.limit,
+            &pagination,
+            sort_column,
+            options,
+        )
+    })
 }
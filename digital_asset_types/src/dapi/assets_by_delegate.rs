@@ -0,0 +1,45 @@
+use crate::dao::scopes;
+use crate::dao::PageOptions;
+use crate::rpc::filter::AssetSorting;
+use crate::rpc::options::Options;
+use crate::rpc::response::AssetList;
+use sea_orm::DatabaseConnection;
+use sea_orm::DbErr;
+
+use super::common::{build_asset_response, create_pagination, create_sorting};
+
+pub async fn get_assets_by_delegate(
+    db: &DatabaseConnection,
+    delegate_address: Vec<u8>,
+    sort_by: AssetSorting,
+    page_options: &PageOptions,
+    options: &Options,
+) -> Result<AssetList, DbErr> {
+    let pagination = create_pagination(page_options)?;
+    let (sort_direction, sort_column) = create_sorting(sort_by);
+    let assets = scopes::asset::get_assets_by_delegate(
+        db,
+        delegate_address.clone(),
+        sort_column,
+        sort_direction,
+        &pagination,
+        page_options.limit,
+        options,
+    )
+    .await?;
+    let grand_total = if options.show_grand_total {
+        Some(scopes::asset::count_assets_by_delegate(db, delegate_address).await?)
+    } else {
+        None
+    };
+    Ok(AssetList {
+        grand_total,
+        ..build_asset_response(
+            assets,
+            page_options.limit,
+            &pagination,
+            sort_column,
+            options,
+        )
+    })
+}
@@ -21,19 +21,28 @@ pub async fn get_assets_by_group(
     let (sort_direction, sort_column) = create_sorting(sorting);
     let assets = scopes::asset::get_by_grouping(
         db,
-        group_key,
-        group_value,
+        group_key.clone(),
+        group_value.clone(),
         sort_column,
         sort_direction,
         &pagination,
         page_options.limit,
-        options.show_unverified_collections,
+        options,
     )
     .await?;
-    Ok(build_asset_response(
-        assets,
-        page_options.limit,
-        &pagination,
-        options,
-    ))
+    let grand_total = if options.show_grand_total {
+        Some(scopes::asset::count_by_grouping(db, group_key, group_value, options).await?)
+    } else {
+        None
+    };
+    Ok(AssetList {
+        grand_total,
+        ..build_asset_response(
+            assets,
+            page_options.limit,
+            &pagination,
+            sort_column,
+            options,
+        )
+    })
 }
@@ -10,7 +10,7 @@ use super::common::{build_asset_response, create_pagination, create_sorting};
 
 pub async fn get_assets_by_owner(
     db: &DatabaseConnection,
-    owner_address: Vec<u8>,
+    owners: Vec<Vec<u8>>,
     sort_by: AssetSorting,
     page_options: &PageOptions,
     options: &Options,
@@ -19,18 +19,27 @@ pub async fn get_assets_by_owner(
     let (sort_direction, sort_column) = create_sorting(sort_by);
     let assets = scopes::asset::get_assets_by_owner(
         db,
-        owner_address,
+        owners.clone(),
         sort_column,
         sort_direction,
         &pagination,
         page_options.limit,
-        options.show_unverified_collections,
+        options,
     )
     .await?;
-    Ok(build_asset_response(
-        assets,
-        page_options.limit,
-        &pagination,
-        options,
-    ))
+    let grand_total = if options.show_grand_total {
+        Some(scopes::asset::count_assets_by_owner(db, owners, options).await?)
+    } else {
+        None
+    };
+    Ok(AssetList {
+        grand_total,
+        ..build_asset_response(
+            assets,
+            page_options.limit,
+            &pagination,
+            sort_column,
+            options,
+        )
+    })
 }
@@ -0,0 +1,44 @@
+use crate::dao::scopes;
+use crate::dao::{Cursor, PageOptions, Pagination};
+use crate::rpc::options::Options;
+use crate::rpc::response::AssetList;
+use sea_orm::DatabaseConnection;
+use sea_orm::DbErr;
+
+use super::common::{build_asset_response, create_pagination};
+
+pub async fn get_assets_by_tree(
+    db: &DatabaseConnection,
+    tree: Vec<u8>,
+    from_leaf: Option<i64>,
+    to_leaf: Option<i64>,
+    page_options: &PageOptions,
+    options: &Options,
+) -> Result<AssetList, DbErr> {
+    let pagination = create_pagination(page_options)?;
+    let assets = scopes::asset::get_assets_by_tree(
+        db,
+        tree,
+        from_leaf,
+        to_leaf,
+        &pagination,
+        page_options.limit,
+        options,
+    )
+    .await?;
+    // Leaves are paged by leaf index rather than by asset id.
+    let cursor = match pagination {
+        Pagination::Cursor(_) => assets.last().and_then(|a| a.asset.nonce).and_then(|nonce| {
+            Cursor {
+                id: Some(nonce.to_be_bytes().to_vec()),
+                sort_value: None,
+            }
+            .encode()
+        }),
+        _ => None,
+    };
+    Ok(AssetList {
+        cursor,
+        ..build_asset_response(assets, page_options.limit, &pagination, None, options)
+    })
+}
@@ -1,12 +1,14 @@
 use sea_orm::sea_query::Expr;
 use sea_orm::{DatabaseConnection, DbBackend};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use {
     crate::dao::asset,
     crate::dao::cl_items,
+    crate::dao::cl_items_history,
+    crate::dao::merkle_tree,
     crate::rpc::AssetProof,
     sea_orm::{entity::*, query::*, DbErr, FromQueryResult},
-    spl_concurrent_merkle_tree::node::empty_node,
+    spl_concurrent_merkle_tree::{hash::recompute, node::empty_node, node::Node},
 };
 
 #[derive(FromQueryResult, Debug, Default, Clone, Eq, PartialEq)]
@@ -33,30 +35,18 @@ struct Leaf {
     leaf_idx: i64,
 }
 
+/// Identifies the tree state a historical proof is built against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofVersion {
+    Seq(i64),
+    Root(Vec<u8>),
+}
+
 pub async fn get_proof_for_asset(
     db: &DatabaseConnection,
     asset_id: Vec<u8>,
 ) -> Result<AssetProof, DbErr> {
-    let sel = cl_items::Entity::find()
-        .join_rev(
-            JoinType::InnerJoin,
-            asset::Entity::belongs_to(cl_items::Entity)
-                .from(asset::Column::Nonce)
-                .to(cl_items::Column::LeafIdx)
-                .into(),
-        )
-        .order_by_desc(cl_items::Column::Seq)
-        .filter(Expr::cust("asset.tree_id = cl_items.tree"))
-        .filter(Expr::cust_with_values(
-            "asset.id = $1::bytea",
-            vec![asset_id],
-        ))
-        .filter(cl_items::Column::Level.eq(0i64));
-    let leaf: Option<cl_items::Model> = sel.one(db).await?;
-    if leaf.is_none() {
-        return Err(DbErr::RecordNotFound("Asset Proof Not Found".to_string()));
-    }
-    let leaf = leaf.unwrap();
+    let leaf = get_leaf_for_asset(db, asset_id).await?;
     let req_indexes = get_required_nodes_for_proof(leaf.node_idx);
     let mut query = cl_items::Entity::find()
         .select_only()
@@ -89,11 +79,193 @@ pub async fn get_proof_for_asset(
     Ok(asset_proof)
 }
 
-pub async fn get_asset_proofs(
+/// Rebuilds the proof for an asset as it was right after the given tree sequence number, from
+/// the latest version of every node at or before it.
+pub async fn get_historical_proof_for_asset(
+    db: &DatabaseConnection,
+    asset_id: Vec<u8>,
+    version: &ProofVersion,
+) -> Result<AssetProof, DbErr> {
+    get_historical_proofs(db, vec![asset_id], version)
+        .await?
+        .pop()
+        .ok_or_else(|| DbErr::RecordNotFound("Asset Proof Not Found".to_string()))?
+        .map(|(_, proof)| proof)
+}
+
+/// Historical proofs for a batch of assets, keyed by asset id. Assets whose tree history doesn't
+/// reach the version are left out.
+pub async fn get_historical_asset_proofs(
     db: &DatabaseConnection,
     asset_ids: Vec<Vec<u8>>,
+    version: &ProofVersion,
 ) -> Result<HashMap<String, AssetProof>, DbErr> {
-    // get the leaves (JOIN with `asset` table to get the asset ids)
+    let mut asset_proofs = HashMap::new();
+    for proof in get_historical_proofs(db, asset_ids, version).await? {
+        match proof {
+            Ok((asset_id, proof)) => {
+                asset_proofs.insert(asset_id, proof);
+            }
+            Err(DbErr::RecordNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(asset_proofs)
+}
+
+/// Rebuilds the proofs of every asset found, looking up the node versions they need across all
+/// their trees in a single query.
+async fn get_historical_proofs(
+    db: &DatabaseConnection,
+    asset_ids: Vec<Vec<u8>>,
+    version: &ProofVersion,
+) -> Result<Vec<Result<(String, AssetProof), DbErr>>, DbErr> {
+    let leaves = get_leaves_for_assets(db, asset_ids).await?;
+    if leaves.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let trees: HashSet<Vec<u8>> = leaves.iter().map(|leaf| leaf.tree_id.clone()).collect();
+    let tree_seqs: HashMap<Vec<u8>, i64> = match version {
+        ProofVersion::Seq(seq) => trees.into_iter().map(|tree| (tree, *seq)).collect(),
+        ProofVersion::Root(root) => {
+            let mut tree_seqs = HashMap::new();
+            for node in cl_items_history::Entity::find()
+                .filter(cl_items_history::Column::Tree.is_in(trees))
+                .filter(cl_items_history::Column::NodeIdx.eq(1i64))
+                .filter(cl_items_history::Column::Hash.eq(root.clone()))
+                .all(db)
+                .await?
+            {
+                let seq = tree_seqs.entry(node.tree).or_insert(node.seq);
+                *seq = node.seq.max(*seq);
+            }
+            tree_seqs
+        }
+    };
+
+    // SELECT DISTINCT ON (tree, node_idx) ... WHERE (tree = ? AND node_idx IN (?) AND seq <= ?) OR ...
+    let mut condition = Condition::any();
+    for leaf in &leaves {
+        let Some(seq) = tree_seqs.get(&leaf.tree_id) else {
+            continue;
+        };
+        let mut node_indexes = get_required_nodes_for_proof(leaf.node_idx);
+        node_indexes.push(leaf.node_idx);
+        condition = condition.add(
+            Condition::all()
+                .add(cl_items_history::Column::Tree.eq(leaf.tree_id.clone()))
+                .add(cl_items_history::Column::NodeIdx.is_in(node_indexes))
+                .add(cl_items_history::Column::Seq.lte(*seq)),
+        );
+    }
+    let mut node_map: HashMap<(Vec<u8>, i64), SimpleChangeLog> = HashMap::new();
+    if !tree_seqs.is_empty() {
+        let mut query = cl_items_history::Entity::find()
+            .select_only()
+            .column(cl_items_history::Column::NodeIdx)
+            .column(cl_items_history::Column::Hash)
+            .column(cl_items_history::Column::Level)
+            .column(cl_items_history::Column::Seq)
+            .column(cl_items_history::Column::Tree)
+            .filter(condition)
+            .order_by_asc(cl_items_history::Column::Tree)
+            .order_by_desc(cl_items_history::Column::NodeIdx)
+            .order_by_desc(cl_items_history::Column::Seq)
+            .build(DbBackend::Postgres);
+        query.sql = query.sql.replace(
+            "SELECT",
+            "SELECT DISTINCT ON (cl_items_history.tree, cl_items_history.node_idx)",
+        );
+        for row in db.query_all(query).await? {
+            let node = SimpleChangeLog::from_query_result(&row, "")?;
+            node_map.insert((node.tree.clone(), node.node_idx), node);
+        }
+    }
+
+    Ok(leaves
+        .into_iter()
+        .map(|leaf| {
+            let seq = *tree_seqs
+                .get(&leaf.tree_id)
+                .ok_or_else(|| DbErr::RecordNotFound("Tree Root Not Found".to_string()))?;
+            let proof = build_historical_proof(&leaf, seq, &node_map)?;
+            Ok((bs58::encode(&leaf.id).into_string(), proof))
+        })
+        .collect())
+}
+
+fn build_historical_proof(
+    leaf: &LeafInfo,
+    seq: i64,
+    node_map: &HashMap<(Vec<u8>, i64), SimpleChangeLog>,
+) -> Result<AssetProof, DbErr> {
+    let node = |node_idx: i64| node_map.get(&(leaf.tree_id.clone(), node_idx));
+
+    // Without a root written at exactly this sequence number, the tree was either not at that
+    // sequence yet or its history has not been indexed.
+    if node(1).map(|root| root.seq) != Some(seq) {
+        return Err(DbErr::RecordNotFound(format!(
+            "Tree History Not Found For Seq {}",
+            seq
+        )));
+    }
+    let req_indexes = get_required_nodes_for_proof(leaf.node_idx);
+    let required_nodes: Vec<SimpleChangeLog> = req_indexes
+        .iter()
+        .filter_map(|node_idx| node(*node_idx).cloned())
+        .collect();
+    let leaf_hash = node(leaf.node_idx)
+        .map(|node| node.hash.clone())
+        .unwrap_or_else(|| empty_node(0).to_vec());
+    let asset_proof = build_asset_proof(
+        leaf.tree_id.clone(),
+        leaf.node_idx,
+        leaf_hash,
+        &req_indexes,
+        &required_nodes,
+    );
+
+    // Nodes written before the history was kept may be missing, in which case the proof does not
+    // lead to the root.
+    if !proof_matches_root(&asset_proof, req_indexes.len() - 1) {
+        return Err(DbErr::RecordNotFound(format!(
+            "Incomplete Tree History For Seq {}",
+            seq
+        )));
+    }
+    Ok(asset_proof)
+}
+
+async fn get_leaf_for_asset(
+    db: &DatabaseConnection,
+    asset_id: Vec<u8>,
+) -> Result<cl_items::Model, DbErr> {
+    let sel = cl_items::Entity::find()
+        .join_rev(
+            JoinType::InnerJoin,
+            asset::Entity::belongs_to(cl_items::Entity)
+                .from(asset::Column::Nonce)
+                .to(cl_items::Column::LeafIdx)
+                .into(),
+        )
+        .order_by_desc(cl_items::Column::Seq)
+        .filter(Expr::cust("asset.tree_id = cl_items.tree"))
+        .filter(Expr::cust_with_values(
+            "asset.id = $1::bytea",
+            vec![asset_id],
+        ))
+        .filter(cl_items::Column::Level.eq(0i64));
+    sel.one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("Asset Proof Not Found".to_string()))
+}
+
+// get the leaves (JOIN with `asset` table to get the asset ids)
+async fn get_leaves_for_assets(
+    db: &DatabaseConnection,
+    asset_ids: Vec<Vec<u8>>,
+) -> Result<Vec<LeafInfo>, DbErr> {
     let q = asset::Entity::find()
         .join(
             JoinType::InnerJoin,
@@ -110,13 +282,20 @@ pub async fn get_asset_proofs(
         .column(cl_items::Column::Hash)
         .filter(Expr::cust("asset.tree_id = cl_items.tree"))
         // filter by user provided asset ids
-        .filter(asset::Column::Id.is_in(asset_ids.clone()))
+        .filter(asset::Column::Id.is_in(asset_ids))
         .build(DbBackend::Postgres);
-    let leaves: Vec<LeafInfo> = db.query_all(q).await.map(|qr| {
-        qr.iter()
-            .map(|q| LeafInfo::from_query_result(q, "").unwrap())
-            .collect()
-    })?;
+    db.query_all(q)
+        .await?
+        .iter()
+        .map(|q| LeafInfo::from_query_result(q, ""))
+        .collect()
+}
+
+pub async fn get_asset_proofs(
+    db: &DatabaseConnection,
+    asset_ids: Vec<Vec<u8>>,
+) -> Result<HashMap<String, AssetProof>, DbErr> {
+    let leaves = get_leaves_for_assets(db, asset_ids).await?;
 
     let mut asset_map: HashMap<Leaf, LeafInfo> = HashMap::new();
     for l in &leaves {
@@ -196,6 +375,46 @@ pub async fn get_asset_proofs(
     Ok(asset_proofs)
 }
 
+/// Reports the shape of each proof's tree, when its account has been indexed.  With
+/// `truncate_canopy`, the proof nodes the tree keeps in its on-chain canopy are dropped.
+pub async fn add_tree_info_to_proofs<'a>(
+    db: &DatabaseConnection,
+    proofs: impl IntoIterator<Item = &'a mut AssetProof>,
+    truncate_canopy: bool,
+) -> Result<(), DbErr> {
+    let mut proofs: Vec<&mut AssetProof> = proofs.into_iter().collect();
+    let tree_ids: Vec<Vec<u8>> = proofs
+        .iter()
+        .filter_map(|proof| bs58::decode(&proof.tree_id).into_vec().ok())
+        .collect();
+    if tree_ids.is_empty() {
+        return Ok(());
+    }
+    let trees: HashMap<String, merkle_tree::Model> = merkle_tree::Entity::find()
+        .filter(merkle_tree::Column::Id.is_in(tree_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|tree| (bs58::encode(&tree.id).into_string(), tree))
+        .collect();
+
+    for proof in proofs.iter_mut() {
+        let Some(tree) = trees.get(&proof.tree_id) else {
+            continue;
+        };
+        // Trees only known from their `CreateTree` instruction have no canopy depth yet.
+        let canopy_depth = (tree.slot_updated > 0).then_some(tree.canopy_depth as u32);
+        proof.max_depth = Some(tree.max_depth as u32);
+        proof.canopy_depth = canopy_depth;
+        proof.max_buffer_size = Some(tree.max_buffer_size as u32);
+        if let (true, Some(canopy_depth)) = (truncate_canopy, canopy_depth) {
+            let len = proof.proof.len().saturating_sub(canopy_depth as usize);
+            proof.proof.truncate(len);
+        }
+    }
+    Ok(())
+}
+
 fn build_asset_proof(
     tree_id: Vec<u8>,
     leaf_node_idx: i64,
@@ -227,9 +446,30 @@ fn build_asset_proof(
             .collect(),
         node_index: leaf_node_idx,
         tree_id: bs58::encode(tree_id).into_string(),
+        max_depth: None,
+        canopy_depth: None,
+        max_buffer_size: None,
     }
 }
 
+fn proof_matches_root(asset_proof: &AssetProof, depth: usize) -> bool {
+    let decode =
+        |node: &String| -> Option<Node> { bs58::decode(node).into_vec().ok()?.try_into().ok() };
+    let (Some(leaf), Some(root)) = (decode(&asset_proof.leaf), decode(&asset_proof.root)) else {
+        return false;
+    };
+    let Some(proof) = asset_proof
+        .proof
+        .iter()
+        .map(decode)
+        .collect::<Option<Vec<Node>>>()
+    else {
+        return false;
+    };
+    let leaf_index = asset_proof.node_index - (1 << depth);
+    recompute(leaf, &proof, leaf_index as u32) == root
+}
+
 fn make_empty_node(lvl: i64, node_index: i64, tree: Vec<u8>) -> SimpleChangeLog {
     SimpleChangeLog {
         node_idx: node_index,
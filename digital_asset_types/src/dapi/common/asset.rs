@@ -1,15 +1,23 @@
 use crate::dao::sea_orm_active_enums::SpecificationVersions;
+use crate::dao::Cursor;
 use crate::dao::FullAsset;
+use crate::dao::HolderCount;
 use crate::dao::PageOptions;
 use crate::dao::Pagination;
-use crate::dao::{asset, asset_authority, asset_creators, asset_data, asset_grouping};
+use crate::dao::SortColumn;
+use crate::dao::{
+    asset, asset_authority, asset_creators, asset_data, asset_events, asset_grouping,
+    extensions::instruction::PascalCase, token_accounts, tokens, AssetSignatures,
+};
 use crate::rpc::filter::{AssetSortBy, AssetSortDirection, AssetSorting};
-use crate::rpc::options::Options;
-use crate::rpc::response::TransactionSignatureList;
-use crate::rpc::response::{AssetError, AssetList};
+use crate::rpc::options::{AssetField, Options};
+use crate::rpc::response::{
+    AssetError, AssetEvent, AssetHistory, AssetList, CollectionHolder, CollectionHolderList,
+};
+use crate::rpc::response::{TokenAccountList, TransactionSignature, TransactionSignatureList};
 use crate::rpc::{
     Asset as RpcAsset, Authority, Compression, Content, Creator, File, Group, Interface,
-    MetadataMap, Ownership, Royalty, Scope, Supply, Uses,
+    MetadataMap, Ownership, Royalty, Scope, Supply, TokenAccount, TokenInfo, Uses,
 };
 use jsonpath_lib::JsonPathError;
 use log::warn;
@@ -51,6 +59,7 @@ pub fn build_asset_response(
     assets: Vec<FullAsset>,
     limit: u64,
     pagination: &Pagination,
+    sort_column: Option<SortColumn>,
     options: &Options,
 ) -> AssetList {
     let total = assets.len() as u32;
@@ -62,18 +71,17 @@ pub fn build_asset_response(
         }
         Pagination::Page { page } => (Some(*page), None, None, None),
         Pagination::Cursor(_) => {
-            if let Some(last_asset) = assets.last() {
-                let cursor_str = bs58::encode(&last_asset.asset.id.clone()).into_string();
-                (None, None, None, Some(cursor_str))
-            } else {
-                (None, None, None, None)
-            }
+            let cursor = assets
+                .last()
+                .and_then(|a| Cursor::from_asset(a, sort_column).encode());
+            (None, None, None, cursor)
         }
     };
 
     let (items, errors) = asset_list_to_rpc(assets, options);
     AssetList {
         total,
+        grand_total: None,
         limit: limit as u32,
         page: page.map(|x| x as u32),
         before,
@@ -84,20 +92,140 @@ pub fn build_asset_response(
     }
 }
 
+pub fn build_collection_holders_response(
+    holders: Vec<HolderCount>,
+    limit: u64,
+    pagination: &Pagination,
+) -> CollectionHolderList {
+    let total = holders.len() as u32;
+    let (page, before, after, cursor) = match pagination {
+        Pagination::Keyset { before, after } => {
+            let bef = before.clone().and_then(|x| String::from_utf8(x).ok());
+            let aft = after.clone().and_then(|x| String::from_utf8(x).ok());
+            (None, bef, aft, None)
+        }
+        Pagination::Page { page } => (Some(*page), None, None, None),
+        Pagination::Cursor(_) => {
+            let cursor = holders.last().and_then(|holder| {
+                Cursor {
+                    id: Some(holder.owner.clone()),
+                    sort_value: None,
+                }
+                .encode()
+            });
+            (None, None, None, cursor)
+        }
+    };
+    CollectionHolderList {
+        total,
+        limit: limit as u32,
+        page: page.map(|x| x as u32),
+        before,
+        after,
+        cursor,
+        items: holders
+            .into_iter()
+            .map(|holder| CollectionHolder {
+                owner: bs58::encode(holder.owner).into_string(),
+                count: holder.count as u64,
+            })
+            .collect(),
+    }
+}
+
+pub fn build_asset_history_response(
+    events: Vec<asset_events::Model>,
+    limit: u64,
+    pagination: &Pagination,
+) -> AssetHistory {
+    let total = events.len() as u32;
+    let (page, cursor) = match pagination {
+        // Events are only paged by number or by a seq cursor.
+        Pagination::Keyset { .. } => (None, None),
+        Pagination::Page { page } => (Some(*page), None),
+        Pagination::Cursor(_) => {
+            let cursor = events.last().and_then(|event| {
+                Cursor {
+                    id: Some(event.seq.to_be_bytes().to_vec()),
+                    sort_value: None,
+                }
+                .encode()
+            });
+            (None, cursor)
+        }
+    };
+    AssetHistory {
+        total,
+        limit: limit as u32,
+        page: page.map(|x| x as u32),
+        cursor,
+        items: events
+            .into_iter()
+            .map(|event| AssetEvent {
+                event: event.event_type.into(),
+                signature: bs58::encode(event.tx).into_string(),
+                slot: event.slot as u64,
+                seq: event.seq as u64,
+                from: event.from_address.map(|x| bs58::encode(x).into_string()),
+                to: event.to_address.map(|x| bs58::encode(x).into_string()),
+            })
+            .collect(),
+    }
+}
+
 pub fn build_transaction_signatures_response(
-    items: Vec<(String, String)>,
+    signatures: AssetSignatures,
     limit: u64,
     pagination: &Pagination,
 ) -> TransactionSignatureList {
+    let (items, last_key) = match signatures {
+        AssetSignatures::Compressed(items) => {
+            let last_key = items.last().map(|item| item.seq.to_be_bytes().to_vec());
+            let items = items
+                .into_iter()
+                .map(|item| TransactionSignature {
+                    signature: bs58::encode(item.tx).into_string(),
+                    instruction: Some(item.instruction.to_pascal_case()),
+                    slot: item.slot.map(|slot| slot as u64),
+                    seq: Some(item.seq as u64),
+                })
+                .collect::<Vec<_>>();
+            (items, last_key)
+        }
+        AssetSignatures::Uncompressed(items) => {
+            let last_key = items
+                .last()
+                .map(|item| [item.slot.to_be_bytes(), item.id.to_be_bytes()].concat());
+            let items = items
+                .into_iter()
+                .map(|item| TransactionSignature {
+                    signature: bs58::encode(item.tx).into_string(),
+                    instruction: None,
+                    slot: Some(item.slot as u64),
+                    seq: None,
+                })
+                .collect::<Vec<_>>();
+            (items, last_key)
+        }
+    };
     let total = items.len() as u32;
-    let (page, before, after) = match pagination {
+    let (page, before, after, cursor) = match pagination {
         Pagination::Keyset { before, after } => {
             let bef = before.clone().and_then(|x| String::from_utf8(x).ok());
             let aft = after.clone().and_then(|x| String::from_utf8(x).ok());
-            (None, bef, aft)
+            (None, bef, aft, None)
+        }
+        Pagination::Page { page } => (Some(*page), None, None, None),
+        Pagination::Cursor(_) => {
+            let cursor = last_key.and_then(|id| {
+                Cursor {
+                    id: Some(id),
+                    sort_value: None,
+                }
+                .encode()
+            });
+            (None, None, None, cursor)
         }
-        Pagination::Page { page } => (Some(*page), None, None),
-        Pagination::Cursor { .. } => (None, None, None),
     };
     TransactionSignatureList {
         total,
@@ -105,16 +233,70 @@ pub fn build_transaction_signatures_response(
         page: page.map(|x| x as u32),
         before,
         after,
+        cursor,
         items,
     }
 }
 
-pub fn create_sorting(sorting: AssetSorting) -> (sea_orm::query::Order, Option<asset::Column>) {
+pub fn build_token_accounts_response(
+    token_accounts: Vec<token_accounts::Model>,
+    limit: u64,
+    pagination: &Pagination,
+) -> TokenAccountList {
+    let total = token_accounts.len() as u32;
+    let (page, before, after, cursor) = match pagination {
+        Pagination::Keyset { before, after } => {
+            let bef = before.clone().and_then(|x| String::from_utf8(x).ok());
+            let aft = after.clone().and_then(|x| String::from_utf8(x).ok());
+            (None, bef, aft, None)
+        }
+        Pagination::Page { page } => (Some(*page), None, None, None),
+        Pagination::Cursor(_) => {
+            let cursor = token_accounts
+                .last()
+                .map(|ta| bs58::encode(&ta.pubkey).into_string());
+            (None, None, None, cursor)
+        }
+    };
+
+    TokenAccountList {
+        total,
+        limit: limit as u32,
+        page: page.map(|x| x as u32),
+        before,
+        after,
+        cursor,
+        token_accounts: token_accounts.into_iter().map(to_token_account).collect(),
+    }
+}
+
+pub fn to_token_account(token_account: token_accounts::Model) -> TokenAccount {
+    TokenAccount {
+        address: bs58::encode(token_account.pubkey).into_string(),
+        mint: bs58::encode(token_account.mint).into_string(),
+        owner: bs58::encode(token_account.owner).into_string(),
+        amount: token_account.amount as u64,
+        delegate: token_account
+            .delegate
+            .map(|d| bs58::encode(d).into_string()),
+        delegated_amount: token_account.delegated_amount as u64,
+        frozen: token_account.frozen,
+        close_authority: token_account
+            .close_authority
+            .map(|c| bs58::encode(c).into_string()),
+        token_program: bs58::encode(token_account.token_program).into_string(),
+    }
+}
+
+pub fn create_sorting(sorting: AssetSorting) -> (sea_orm::query::Order, Option<SortColumn>) {
     let sort_column = match sorting.sort_by {
-        AssetSortBy::Id => Some(asset::Column::Id),
-        AssetSortBy::Created => Some(asset::Column::CreatedAt),
-        AssetSortBy::Updated => Some(asset::Column::SlotUpdated),
-        AssetSortBy::RecentAction => Some(asset::Column::SlotUpdated),
+        AssetSortBy::Id => Some(SortColumn::Id),
+        AssetSortBy::Created => Some(SortColumn::CreatedAt),
+        AssetSortBy::Updated => Some(SortColumn::SlotUpdated),
+        AssetSortBy::RecentAction => Some(SortColumn::RecentAction),
+        AssetSortBy::Name => Some(SortColumn::Name),
+        AssetSortBy::RoyaltyAmount => Some(SortColumn::RoyaltyAmount),
+        AssetSortBy::Supply => Some(SortColumn::Supply),
         AssetSortBy::None => None,
     };
     let sort_direction = match sorting.sort_direction.unwrap_or_default() {
@@ -332,6 +514,19 @@ pub fn to_grouping(
     Ok(result)
 }
 
+pub fn to_token_info(token: tokens::Model, balance: Option<i64>) -> TokenInfo {
+    TokenInfo {
+        supply: token.supply as u64,
+        decimals: token.decimals as u8,
+        token_program: bs58::encode(token.token_program).into_string(),
+        mint_authority: token.mint_authority.map(|a| bs58::encode(a).into_string()),
+        freeze_authority: token
+            .freeze_authority
+            .map(|a| bs58::encode(a).into_string()),
+        balance: balance.map(|b| b as u64),
+    }
+}
+
 pub fn get_interface(asset: &asset::Model) -> Result<Interface, DbErr> {
     Ok(Interface::from((
         asset
@@ -355,12 +550,20 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
         authorities,
         creators,
         groups,
+        token_info,
+        token_balance,
     } = asset;
-    let rpc_authorities = to_authority(authorities);
-    let rpc_creators = to_creators(creators);
-    let rpc_groups = to_grouping(groups, options)?;
     let interface = get_interface(&asset)?;
-    let content = get_content(&asset, &data)?;
+    let content = if options.includes(AssetField::Content) {
+        Some(get_content(&asset, &data)?)
+    } else {
+        None
+    };
+    let grouping = if options.includes(AssetField::Grouping) {
+        Some(to_grouping(groups, options)?)
+    } else {
+        None
+    };
     let mut chain_data_selector_fn = jsonpath_lib::selector(&data.chain_data);
     let chain_data_selector = &mut chain_data_selector_fn;
     let basis_points = safe_select(chain_data_selector, "$.primary_sale_happened")
@@ -371,33 +574,37 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
     Ok(RpcAsset {
         interface: interface.clone(),
         id: bs58::encode(asset.id).into_string(),
-        content: Some(content),
-        authorities: Some(rpc_authorities),
+        content,
+        authorities: options
+            .includes(AssetField::Authorities)
+            .then(|| to_authority(authorities)),
         mutable: data.chain_data_mutability.into(),
-        compression: Some(Compression {
-            eligible: asset.compressible,
-            compressed: asset.compressed,
-            leaf_id: asset.nonce.unwrap_or(0),
-            seq: asset.seq.unwrap_or(0),
-            tree: asset
-                .tree_id
-                .map(|s| bs58::encode(s).into_string())
-                .unwrap_or_default(),
-            asset_hash: asset
-                .leaf
-                .map(|s| bs58::encode(s).into_string())
-                .unwrap_or_default(),
-            data_hash: asset
-                .data_hash
-                .map(|e| if asset.compressed { e.trim() } else { "" }.to_string())
-                .unwrap_or_default(),
-            creator_hash: asset
-                .creator_hash
-                .map(|e| if asset.compressed { e.trim() } else { "" }.to_string())
-                .unwrap_or_default(),
-        }),
-        grouping: Some(rpc_groups),
-        royalty: Some(Royalty {
+        compression: options
+            .includes(AssetField::Compression)
+            .then(|| Compression {
+                eligible: asset.compressible,
+                compressed: asset.compressed,
+                leaf_id: asset.nonce.unwrap_or(0),
+                seq: asset.seq.unwrap_or(0),
+                tree: asset
+                    .tree_id
+                    .map(|s| bs58::encode(s).into_string())
+                    .unwrap_or_default(),
+                asset_hash: asset
+                    .leaf
+                    .map(|s| bs58::encode(s).into_string())
+                    .unwrap_or_default(),
+                data_hash: asset
+                    .data_hash
+                    .map(|e| if asset.compressed { e.trim() } else { "" }.to_string())
+                    .unwrap_or_default(),
+                creator_hash: asset
+                    .creator_hash
+                    .map(|e| if asset.compressed { e.trim() } else { "" }.to_string())
+                    .unwrap_or_default(),
+            }),
+        grouping,
+        royalty: options.includes(AssetField::Royalty).then(|| Royalty {
             royalty_model: asset.royalty_target_type.into(),
             target: asset.royalty_target.map(|s| bs58::encode(s).into_string()),
             percent: (asset.royalty_amount as f64) * 0.0001,
@@ -405,7 +612,9 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
             primary_sale_happened: basis_points,
             locked: false,
         }),
-        creators: Some(rpc_creators),
+        creators: options
+            .includes(AssetField::Creators)
+            .then(|| to_creators(creators)),
         ownership: Ownership {
             frozen: asset.frozen,
             delegated: asset.delegate.is_some(),
@@ -417,24 +626,31 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
                 .unwrap_or("".to_string()),
         },
         supply: match interface {
-            Interface::V1NFT => Some(Supply {
+            Interface::V1NFT if options.includes(AssetField::Supply) => Some(Supply {
                 edition_nonce,
                 print_current_supply: 0,
                 print_max_supply: 0,
             }),
             _ => None,
         },
-        uses: data.chain_data.get("uses").map(|u| Uses {
-            use_method: u
-                .get("use_method")
-                .and_then(|s| s.as_str())
-                .unwrap_or("Single")
-                .to_string()
-                .into(),
-            total: u.get("total").and_then(|t| t.as_u64()).unwrap_or(0),
-            remaining: u.get("remaining").and_then(|t| t.as_u64()).unwrap_or(0),
-        }),
+        uses: data
+            .chain_data
+            .get("uses")
+            .filter(|_| options.includes(AssetField::Uses))
+            .map(|u| Uses {
+                use_method: u
+                    .get("use_method")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or("Single")
+                    .to_string()
+                    .into(),
+                total: u.get("total").and_then(|t| t.as_u64()).unwrap_or(0),
+                remaining: u.get("remaining").and_then(|t| t.as_u64()).unwrap_or(0),
+            }),
         burnt: asset.burnt,
+        token_info: token_info
+            .filter(|_| options.includes(AssetField::TokenInfo))
+            .map(|t| to_token_info(t, token_balance)),
     })
 }
 
@@ -0,0 +1,31 @@
+use crate::dao::scopes;
+use crate::dao::PageOptions;
+
+use crate::rpc::filter::AssetSortDirection;
+use crate::rpc::response::AssetHistory;
+use sea_orm::DatabaseConnection;
+use sea_orm::DbErr;
+
+use super::common::{build_asset_history_response, create_pagination};
+
+pub async fn get_asset_history(
+    db: &DatabaseConnection,
+    asset_id: Vec<u8>,
+    page_options: PageOptions,
+    sort_direction: Option<AssetSortDirection>,
+) -> Result<AssetHistory, DbErr> {
+    let pagination = create_pagination(&page_options)?;
+    let events = scopes::asset::get_asset_events(
+        db,
+        asset_id,
+        &pagination,
+        page_options.limit,
+        sort_direction,
+    )
+    .await?;
+    Ok(build_asset_history_response(
+        events,
+        page_options.limit,
+        &pagination,
+    ))
+}
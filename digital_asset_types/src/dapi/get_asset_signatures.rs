@@ -1,4 +1,5 @@
 use crate::dao::scopes;
+use crate::dao::sea_orm_active_enums::Instruction;
 use crate::dao::PageOptions;
 
 use crate::rpc::filter::AssetSortDirection;
@@ -13,6 +14,7 @@ pub async fn get_asset_signatures(
     asset_id: Option<Vec<u8>>,
     tree: Option<Vec<u8>>,
     leaf_idx: Option<i64>,
+    instruction: Option<Instruction>,
     page_options: PageOptions,
     sort_direction: Option<AssetSortDirection>,
 ) -> Result<TransactionSignatureList, DbErr> {
@@ -22,6 +24,7 @@ pub async fn get_asset_signatures(
         asset_id,
         tree,
         leaf_idx,
+        instruction,
         &pagination,
         page_options.limit,
         sort_direction,
@@ -0,0 +1,24 @@
+use crate::dao::scopes;
+use crate::dao::PageOptions;
+
+use crate::rpc::response::CollectionHolderList;
+use sea_orm::DatabaseConnection;
+use sea_orm::DbErr;
+
+use super::common::{build_collection_holders_response, create_pagination};
+
+pub async fn get_collection_holders(
+    db: &DatabaseConnection,
+    collection: String,
+    page_options: &PageOptions,
+) -> Result<CollectionHolderList, DbErr> {
+    let pagination = create_pagination(page_options)?;
+    let holders =
+        scopes::asset::get_collection_holders(db, collection, &pagination, page_options.limit)
+            .await?;
+    Ok(build_collection_holders_response(
+        holders,
+        page_options.limit,
+        &pagination,
+    ))
+}
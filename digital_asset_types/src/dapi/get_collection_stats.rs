@@ -0,0 +1,76 @@
+use crate::dao::asset_data;
+use crate::rpc::response::CollectionStats;
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult, Statement};
+
+#[derive(FromQueryResult)]
+struct MemberCounts {
+    group_size: i64,
+    unique_holders: i64,
+    burnt: i64,
+    compressed: i64,
+    unverified: i64,
+    first_mint_slot: Option<i64>,
+    last_mint_slot: Option<i64>,
+}
+
+pub async fn get_collection_stats(
+    db: &DatabaseConnection,
+    collection: Vec<u8>,
+) -> Result<CollectionStats, DbErr> {
+    let group_value = bs58::encode(&collection).into_string();
+
+    // Compressed assets carry their mint slot in the bubblegum history, uncompressed ones fall
+    // back to the slot their mint account was indexed at.
+    let counts = MemberCounts::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT
+            COUNT(*) FILTER (WHERE verified) AS group_size,
+            COUNT(DISTINCT owner) FILTER (WHERE verified AND NOT burnt) AS unique_holders,
+            COUNT(*) FILTER (WHERE verified AND burnt) AS burnt,
+            COUNT(*) FILTER (WHERE verified AND compressed) AS compressed,
+            COUNT(*) FILTER (WHERE NOT verified) AS unverified,
+            MIN(mint_slot) FILTER (WHERE verified) AS first_mint_slot,
+            MAX(mint_slot) FILTER (WHERE verified) AS last_mint_slot
+        FROM (
+            SELECT
+                COALESCE(asset_grouping.verified, true) AS verified,
+                asset.owner,
+                asset.burnt,
+                asset.compressed,
+                COALESCE(
+                    (SELECT MIN(slot) FROM asset_events
+                     WHERE asset_events.asset_id = asset.id AND event_type = 'mint'),
+                    asset.slot_updated_mint_account
+                ) AS mint_slot
+            FROM asset_grouping
+            INNER JOIN asset ON asset.id = asset_grouping.asset_id
+            WHERE asset_grouping.group_key = 'collection' AND asset_grouping.group_value = $1
+        ) members",
+        vec![group_value.clone().into()],
+    ))
+    .one(db)
+    .await?
+    .ok_or_else(|| DbErr::RecordNotFound("Collection Not Found".to_string()))?;
+
+    let collection_data = asset_data::Entity::find_by_id(collection).one(db).await?;
+    let name = collection_data
+        .as_ref()
+        .and_then(|data| data.chain_data.get("name")?.as_str().map(str::to_string));
+    let image = collection_data
+        .as_ref()
+        .and_then(|data| data.metadata.get("image")?.as_str().map(str::to_string));
+
+    Ok(CollectionStats {
+        collection: group_value,
+        name,
+        image,
+        group_size: counts.group_size as u64,
+        unique_holders: counts.unique_holders as u64,
+        burnt: counts.burnt as u64,
+        compressed: counts.compressed as u64,
+        uncompressed: (counts.group_size - counts.compressed) as u64,
+        unverified: counts.unverified as u64,
+        first_mint_slot: counts.first_mint_slot.map(|slot| slot as u64),
+        last_mint_slot: counts.last_mint_slot.map(|slot| slot as u64),
+    })
+}
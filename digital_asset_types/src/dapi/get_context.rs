@@ -0,0 +1,32 @@
+use crate::{dao::STALE_PROGRESS_SECONDS, rpc::response::RpcContext};
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement};
+
+#[derive(FromQueryResult)]
+struct ProcessedSlot {
+    slot: Option<i64>,
+}
+
+/// The slot every consumer of the ingester streams has processed through, short of messages
+/// that failed and wait to be retried. Consumers and failures that have not been heard from
+/// recently are left out. 0 before any stream made progress.
+pub async fn get_context(db: &DatabaseConnection) -> Result<RpcContext, DbErr> {
+    let processed = ProcessedSlot::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        format!(
+            "SELECT MIN(slot) AS slot FROM (
+                SELECT slot, updated_at FROM ingester_progress
+                UNION ALL
+                SELECT slot - 1, updated_at FROM ingester_failed_messages
+            ) progress
+            WHERE updated_at >= LOCALTIMESTAMP - INTERVAL '{} seconds'",
+            STALE_PROGRESS_SECONDS
+        ),
+    ))
+    .one(db)
+    .await?;
+    Ok(RpcContext {
+        slot: processed
+            .and_then(|p| p.slot)
+            .map_or(0, |slot| slot.max(0) as u64),
+    })
+}
@@ -0,0 +1,166 @@
+use super::get_context;
+use crate::{
+    dao::STALE_PROGRESS_SECONDS,
+    rpc::response::{BackfillStatus, IndexerHealth, IndexerStatus, StreamStatus, TaskCounts},
+};
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement};
+
+#[derive(FromQueryResult)]
+struct StreamProgress {
+    stream: String,
+    slot: i64,
+    newest_slot: i64,
+    lag_seconds: i64,
+    backlog: Option<i64>,
+}
+
+#[derive(FromQueryResult)]
+struct Counts {
+    pending: i64,
+    failed: i64,
+}
+
+/// Thresholds the ingester streams must stay within for the index to be healthy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HealthLimits {
+    /// Slots a stream may trail the newest slot processed by any stream by.
+    pub max_lag_slots: Option<u64>,
+    /// Seconds a stream may go without progress.
+    pub max_lag_seconds: Option<u64>,
+    /// Messages that may be waiting in a stream.
+    pub max_backlog: Option<u64>,
+}
+
+impl HealthLimits {
+    const fn is_set(&self) -> bool {
+        self.max_lag_slots.is_some() || self.max_lag_seconds.is_some() || self.max_backlog.is_some()
+    }
+
+    /// Why `stream` is outside of the limits, if it is.
+    pub fn violation(&self, stream: &StreamStatus) -> Option<String> {
+        if let Some(max) = self.max_lag_slots.filter(|max| stream.lag_slots > *max) {
+            return Some(format!(
+                "stream {} is {} slots behind, more than {}",
+                stream.stream, stream.lag_slots, max
+            ));
+        }
+        // Streams that all stopped together don't fall behind each other, but go without
+        // progress.
+        if let Some(max) = self.max_lag_seconds.filter(|max| stream.lag_seconds > *max) {
+            return Some(format!(
+                "stream {} made no progress for {}s, more than {}s",
+                stream.stream, stream.lag_seconds, max
+            ));
+        }
+        match (self.max_backlog, stream.backlog) {
+            (Some(max), Some(backlog)) if backlog > max => Some(format!(
+                "stream {} has {} messages waiting, more than {}",
+                stream.stream, backlog, max
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Reports how far behind the ingester streams are. Once any limit is set, the index is only
+/// healthy while streams have made progress and all of them are within the limits.
+pub async fn get_indexer_health(
+    db: &DatabaseConnection,
+    limits: HealthLimits,
+) -> Result<IndexerHealth, DbErr> {
+    // A stream is through the slot all of its recently active consumers are through, short of its
+    // failed messages, and lags behind the newest slot any consumer has processed. Progress is
+    // timestamped by the database clock, so the time since the last progress of any of its
+    // consumers is measured against it too.
+    let progress = StreamProgress::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        format!(
+            "SELECT
+                stream,
+                COALESCE(
+                    MIN(slot) FILTER (WHERE updated_at >= LOCALTIMESTAMP - INTERVAL '{} seconds'),
+                    MAX(slot)
+                ) AS slot,
+                MAX(slot) FILTER (WHERE progressed_at IS NOT NULL) AS newest_slot,
+                GREATEST(EXTRACT(EPOCH FROM (LOCALTIMESTAMP - MAX(progressed_at))), 0)::bigint
+                    AS lag_seconds,
+                MAX(backlog) AS backlog
+            FROM (
+                SELECT stream, slot, updated_at, updated_at AS progressed_at, backlog
+                FROM ingester_progress
+                UNION ALL
+                SELECT stream, slot - 1, updated_at, NULL, NULL
+                FROM ingester_failed_messages
+            ) progress
+            GROUP BY stream
+            HAVING MAX(progressed_at) IS NOT NULL
+            ORDER BY stream",
+            STALE_PROGRESS_SECONDS
+        ),
+    ))
+    .all(db)
+    .await?;
+    let newest_slot = progress.iter().map(|p| p.newest_slot).max().unwrap_or(0);
+    let streams: Vec<StreamStatus> = progress
+        .into_iter()
+        .map(|p| StreamStatus {
+            stream: p.stream,
+            slot: p.slot.max(0) as u64,
+            lag_slots: (newest_slot - p.slot).max(0) as u64,
+            lag_seconds: p.lag_seconds as u64,
+            backlog: p.backlog.map(|b| b as u64),
+        })
+        .collect();
+
+    let healthy = !limits.is_set()
+        || (!streams.is_empty() && streams.iter().all(|s| limits.violation(s).is_none()));
+    let context = get_context(db).await?;
+    Ok(IndexerHealth {
+        healthy,
+        context_slot: context.slot,
+        streams,
+        replicas: vec![],
+    })
+}
+
+/// Reports how far behind the index is, along with the background work waiting to be done.
+pub async fn get_indexer_status(
+    db: &DatabaseConnection,
+    limits: HealthLimits,
+) -> Result<IndexerStatus, DbErr> {
+    let health = get_indexer_health(db, limits).await?;
+
+    let tasks = Counts::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT
+            COUNT(*) FILTER (WHERE status = 'pending') AS pending,
+            COUNT(*) FILTER (WHERE status = 'failed') AS failed
+        FROM tasks"
+            .to_string(),
+    ))
+    .one(db)
+    .await?;
+
+    let backfill = Counts::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT
+            COUNT(DISTINCT tree) FILTER (WHERE NOT backfilled AND NOT failed) AS pending,
+            COUNT(DISTINCT tree) FILTER (WHERE failed) AS failed
+        FROM backfill_items"
+            .to_string(),
+    ))
+    .one(db)
+    .await?;
+
+    Ok(IndexerStatus {
+        health,
+        tasks: tasks.map_or_else(TaskCounts::default, |c| TaskCounts {
+            pending: c.pending as u64,
+            failed: c.failed as u64,
+        }),
+        backfill: backfill.map_or_else(BackfillStatus::default, |c| BackfillStatus {
+            pending_trees: c.pending as u64,
+            failed_trees: c.failed as u64,
+        }),
+    })
+}
@@ -0,0 +1,103 @@
+use crate::dao::{asset_data, PageOptions};
+use crate::rpc::response::{OwnerCollection, OwnerCollections};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult, QueryFilter,
+    Statement,
+};
+use std::collections::HashMap;
+
+#[derive(FromQueryResult)]
+struct Total {
+    total: i64,
+}
+
+#[derive(FromQueryResult)]
+struct CollectionCount {
+    collection: Option<String>,
+    verified: bool,
+    count: i64,
+    sample_asset: Vec<u8>,
+}
+
+pub async fn get_owner_collections(
+    db: &DatabaseConnection,
+    owner: Vec<u8>,
+    page_options: &PageOptions,
+) -> Result<OwnerCollections, DbErr> {
+    let page = page_options.page.unwrap_or(1).max(1);
+    // Assets outside any collection share the row with a null collection. Postgres has no MIN
+    // for bytea, but hex encoding keeps the order of the ids.
+    let counts = CollectionCount::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT
+            asset_grouping.group_value AS collection,
+            COALESCE(asset_grouping.verified, asset_grouping.id IS NOT NULL) AS verified,
+            COUNT(*) AS count,
+            DECODE(MIN(ENCODE(asset.id, 'hex')), 'hex') AS sample_asset
+        FROM asset
+        LEFT JOIN asset_grouping ON asset_grouping.asset_id = asset.id
+            AND asset_grouping.group_key = 'collection'
+            AND asset_grouping.group_value IS NOT NULL
+        WHERE asset.owner = $1 AND asset.supply > 0 AND NOT asset.burnt
+        GROUP BY 1, 2
+        ORDER BY count DESC, collection NULLS LAST, verified DESC
+        LIMIT $2 OFFSET $3",
+        vec![
+            owner.clone().into(),
+            (page_options.limit as i64).into(),
+            (((page - 1) * page_options.limit) as i64).into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    let total = Total::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT COUNT(*) AS total FROM asset
+        WHERE asset.owner = $1 AND asset.supply > 0 AND NOT asset.burnt",
+        vec![owner.clone().into()],
+    ))
+    .one(db)
+    .await?
+    .map_or(0, |row| row.total as u64);
+
+    let collection_ids = counts
+        .iter()
+        .filter_map(|row| bs58::decode(row.collection.as_ref()?).into_vec().ok())
+        .collect::<Vec<_>>();
+    let collection_data = asset_data::Entity::find()
+        .filter(asset_data::Column::Id.is_in(collection_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|data| (bs58::encode(&data.id).into_string(), data))
+        .collect::<HashMap<_, _>>();
+
+    let items = counts
+        .into_iter()
+        .map(|row| {
+            let data = row
+                .collection
+                .as_ref()
+                .and_then(|collection| collection_data.get(collection));
+            OwnerCollection {
+                name: data
+                    .and_then(|data| data.chain_data.get("name")?.as_str().map(str::to_string)),
+                image: data
+                    .and_then(|data| data.metadata.get("image")?.as_str().map(str::to_string)),
+                collection: row.collection,
+                verified: row.verified,
+                count: row.count as u64,
+                sample_asset: bs58::encode(row.sample_asset).into_string(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(OwnerCollections {
+        owner: bs58::encode(owner).into_string(),
+        total,
+        limit: page_options.limit as u32,
+        page: page_options.page.map(|page| page as u32),
+        items,
+    })
+}
@@ -0,0 +1,29 @@
+use crate::dao::scopes;
+use crate::dao::PageOptions;
+use crate::rpc::response::TokenAccountList;
+use sea_orm::DatabaseConnection;
+use sea_orm::DbErr;
+
+use super::common::{build_token_accounts_response, create_pagination};
+
+pub async fn get_token_accounts(
+    db: &DatabaseConnection,
+    owner_address: Option<Vec<u8>>,
+    mint_address: Option<Vec<u8>>,
+    page_options: &PageOptions,
+) -> Result<TokenAccountList, DbErr> {
+    let pagination = create_pagination(page_options)?;
+    let token_accounts = scopes::asset::get_token_accounts(
+        db,
+        owner_address,
+        mint_address,
+        &pagination,
+        page_options.limit,
+    )
+    .await?;
+    Ok(build_token_accounts_response(
+        token_accounts,
+        page_options.limit,
+        &pagination,
+    ))
+}
@@ -0,0 +1,73 @@
+use crate::dao::{cl_items_history, merkle_tree};
+use crate::rpc::response::{SeqGap, TreeInfo};
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait,
+    FromQueryResult, QueryFilter, QuerySelect, Statement,
+};
+
+/// Upper bound on the gaps reported for a tree, badly indexed trees can have a lot of them.
+const MAX_REPORTED_GAPS: u64 = 1000;
+
+#[derive(FromQueryResult)]
+struct MaxSeq {
+    max_seq: Option<i64>,
+}
+
+#[derive(FromQueryResult)]
+struct Gap {
+    start_seq: i64,
+    end_seq: i64,
+}
+
+pub async fn get_tree_info(db: &DatabaseConnection, tree_id: Vec<u8>) -> Result<TreeInfo, DbErr> {
+    let tree = merkle_tree::Entity::find_by_id(tree_id.clone())
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("Tree Not Found".to_string()))?;
+
+    // Every change rewrites the root, so its history holds each indexed sequence number.
+    let indexed_seq = cl_items_history::Entity::find()
+        .select_only()
+        .column_as(Expr::col(cl_items_history::Column::Seq).max(), "max_seq")
+        .filter(cl_items_history::Column::Tree.eq(tree_id.clone()))
+        .filter(cl_items_history::Column::NodeIdx.eq(1i64))
+        .into_model::<MaxSeq>()
+        .one(db)
+        .await?
+        .and_then(|row| row.max_seq);
+
+    let gaps = Gap::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT prev_seq + 1 AS start_seq, seq - 1 AS end_seq FROM (
+            SELECT seq, LAG(seq, 1, 0::bigint) OVER (ORDER BY seq) AS prev_seq
+            FROM cl_items_history WHERE tree = $1 AND node_idx = 1
+        ) seqs WHERE seq - prev_seq > 1 ORDER BY seq LIMIT $2",
+        vec![tree_id.into(), MAX_REPORTED_GAPS.into()],
+    ))
+    .all(db)
+    .await?;
+
+    Ok(TreeInfo {
+        tree_id: bs58::encode(&tree.id).into_string(),
+        max_depth: tree.max_depth as u32,
+        max_buffer_size: tree.max_buffer_size as u32,
+        canopy_depth: (tree.slot_updated > 0).then_some(tree.canopy_depth as u32),
+        tree_config: tree.tree_config.map(|key| bs58::encode(key).into_string()),
+        tree_creator: tree.tree_creator.map(|key| bs58::encode(key).into_string()),
+        tree_delegate: tree
+            .tree_delegate
+            .map(|key| bs58::encode(key).into_string()),
+        is_public: tree.is_public,
+        num_minted: tree.num_minted.map(|n| n as u64),
+        total_mint_capacity: tree.total_mint_capacity.map(|n| n as u64),
+        on_chain_seq: tree.seq.map(|seq| seq as u64),
+        indexed_seq: indexed_seq.map(|seq| seq as u64),
+        gaps: gaps
+            .into_iter()
+            .map(|gap| SeqGap {
+                start: gap.start_seq as u64,
+                end: gap.end_seq as u64,
+            })
+            .collect(),
+    })
+}
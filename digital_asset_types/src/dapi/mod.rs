@@ -1,19 +1,39 @@
 mod assets_by_authority;
 mod assets_by_creator;
+mod assets_by_delegate;
 mod assets_by_group;
 mod assets_by_owner;
+mod assets_by_tree;
 mod change_logs;
 mod get_asset;
+mod get_asset_history;
 mod get_asset_signatures;
+mod get_collection_holders;
+mod get_collection_stats;
+mod get_context;
+mod get_indexer_status;
+mod get_owner_collections;
+mod get_token_accounts;
+mod get_tree_info;
 mod search_assets;
 
 pub mod common;
 
 pub use assets_by_authority::*;
 pub use assets_by_creator::*;
+pub use assets_by_delegate::*;
 pub use assets_by_group::*;
 pub use assets_by_owner::*;
+pub use assets_by_tree::*;
 pub use change_logs::*;
 pub use get_asset::*;
+pub use get_asset_history::*;
 pub use get_asset_signatures::*;
+pub use get_collection_holders::*;
+pub use get_collection_stats::*;
+pub use get_context::*;
+pub use get_indexer_status::*;
+pub use get_owner_collections::*;
+pub use get_token_accounts::*;
+pub use get_tree_info::*;
 pub use search_assets::*;
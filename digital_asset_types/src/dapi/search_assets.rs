@@ -15,7 +15,7 @@ pub async fn search_assets(
     let pagination = create_pagination(page_options)?;
     let (sort_direction, sort_column) = create_sorting(sorting);
     let (condition, joins) = search_assets_query.conditions()?;
-    let assets = scopes::asset::get_assets_by_condition(
+    let mut assets = scopes::asset::get_assets_by_condition(
         db,
         condition,
         joins,
@@ -23,13 +23,29 @@ pub async fn search_assets(
         sort_direction,
         &pagination,
         page_options.limit,
-        options.show_unverified_collections,
+        options,
     )
     .await?;
-    Ok(build_asset_response(
-        assets,
-        page_options.limit,
-        &pagination,
-        options,
-    ))
+    let grand_total = if options.show_grand_total {
+        let (condition, joins) = search_assets_query.conditions()?;
+        Some(scopes::asset::count_assets_by_condition(db, condition, joins).await?)
+    } else {
+        None
+    };
+    if let (true, Some(owners)) = (
+        search_assets_query.show_fungible,
+        search_assets_query.owner_addresses,
+    ) {
+        scopes::asset::add_owner_token_balances(db, owners, &mut assets).await?;
+    }
+    Ok(AssetList {
+        grand_total,
+        ..build_asset_response(
+            assets,
+            page_options.limit,
+            &pagination,
+            sort_column,
+            options,
+        )
+    })
 }
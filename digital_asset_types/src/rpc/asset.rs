@@ -18,6 +18,12 @@ pub struct AssetProof {
     pub node_index: i64,
     pub leaf: String,
     pub tree_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canopy_depth: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_buffer_size: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
@@ -34,6 +40,8 @@ pub enum Interface {
     Nft,
     #[serde(rename = "FungibleAsset")]
     FungibleAsset,
+    #[serde(rename = "FungibleToken")]
+    FungibleToken,
     #[serde(rename = "Custom")]
     Custom,
     #[serde(rename = "Identity")]
@@ -53,6 +61,12 @@ impl From<(&SpecificationVersions, &SpecificationAssetClass)> for Interface {
             (SpecificationVersions::V1, SpecificationAssetClass::ProgrammableNft) => {
                 Interface::ProgrammableNFT
             }
+            (SpecificationVersions::V1, SpecificationAssetClass::FungibleAsset) => {
+                Interface::FungibleAsset
+            }
+            (SpecificationVersions::V1, SpecificationAssetClass::FungibleToken) => {
+                Interface::FungibleToken
+            }
             _ => Interface::Custom,
         }
     }
@@ -72,6 +86,10 @@ impl From<Interface> for (SpecificationVersions, SpecificationAssetClass) {
                 SpecificationVersions::V1,
                 SpecificationAssetClass::FungibleAsset,
             ),
+            Interface::FungibleToken => (
+                SpecificationVersions::V1,
+                SpecificationAssetClass::FungibleToken,
+            ),
             _ => (SpecificationVersions::V1, SpecificationAssetClass::Unknown),
         }
     }
@@ -342,6 +360,17 @@ pub struct Supply {
     pub edition_nonce: Option<u64>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TokenInfo {
+    pub supply: u64,
+    pub decimals: u8,
+    pub token_program: String,
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<u64>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Asset {
     pub interface: Interface,
@@ -361,7 +390,23 @@ pub struct Asset {
     pub ownership: Ownership,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub uses: Option<Uses>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub supply: Option<Supply>,
     pub mutable: bool,
     pub burnt: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_info: Option<TokenInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TokenAccount {
+    pub address: String,
+    pub mint: String,
+    pub owner: String,
+    pub amount: u64,
+    pub delegate: Option<String>,
+    pub delegated_amount: u64,
+    pub frozen: bool,
+    pub close_authority: Option<String>,
+    pub token_program: String,
 }
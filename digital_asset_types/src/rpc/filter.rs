@@ -1,3 +1,4 @@
+use super::{Interface, OwnershipModel, RoyaltyModel};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +28,12 @@ pub enum AssetSortBy {
     Updated,
     #[serde(rename = "recent_action")]
     RecentAction,
+    #[serde(rename = "name")]
+    Name,
+    #[serde(rename = "royalty_amount")]
+    RoyaltyAmount,
+    #[serde(rename = "supply")]
+    Supply,
     #[serde(rename = "none")]
     None,
 }
@@ -47,3 +54,68 @@ pub enum SearchConditionType {
     #[serde(rename = "any")]
     Any,
 }
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, JsonSchema)]
+pub enum TextMatchMode {
+    #[serde(rename = "exact")]
+    Exact,
+    #[serde(rename = "prefix")]
+    Prefix,
+    #[serde(rename = "contains")]
+    #[default]
+    Contains,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct AttributeFilter {
+    /// Whether an asset must match all or any of the traits. Defaults to all.
+    #[serde(default)]
+    pub condition_type: Option<SearchConditionType>,
+    pub traits: Vec<TraitFilter>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct TraitFilter {
+    #[serde(alias = "trait_type")]
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// A node of a `searchAssets` filter tree: either a group combining other nodes or a single
+/// condition on the asset, e.g. `{"all": [{"any": [{"grouping": ["collection", "A"]},
+/// {"grouping": ["collection", "B"]}]}, {"not": {"burnt": true}}]}`.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub enum SearchFilter {
+    /// Matches when every node of the group matches.
+    All(Vec<SearchFilter>),
+    /// Matches when at least one node of the group matches.
+    Any(Vec<SearchFilter>),
+    /// Matches when the node does not.
+    Not(Box<SearchFilter>),
+    Interface(Interface),
+    OwnerAddress(String),
+    OwnerType(OwnershipModel),
+    CreatorAddress(String),
+    /// Matches assets with at least one creator of the given verification status.
+    CreatorVerified(bool),
+    AuthorityAddress(String),
+    /// `(group_key, group_value)`
+    Grouping((String, String)),
+    Delegate(String),
+    Frozen(bool),
+    Supply(u64),
+    SupplyMint(String),
+    Compressed(bool),
+    Compressible(bool),
+    RoyaltyTargetType(RoyaltyModel),
+    RoyaltyTarget(String),
+    RoyaltyAmount(u32),
+    Burnt(bool),
+    JsonUri(String),
+    /// Matched like the request's `name`, following its `textMatchMode` and `caseInsensitive`.
+    Name(String),
+    Symbol(String),
+}
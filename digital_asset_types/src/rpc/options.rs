@@ -6,4 +6,41 @@ use serde::{Deserialize, Serialize};
 pub struct Options {
     #[serde(default)]
     pub show_unverified_collections: bool,
+    #[serde(default)]
+    pub show_fungible: bool,
+    #[serde(default)]
+    pub show_grand_total: bool,
+    /// Sections of each asset to return, all of them when unset.
+    #[serde(default)]
+    pub fields: Option<Vec<AssetField>>,
+}
+
+impl Options {
+    /// Whether `field` was requested.
+    pub fn includes(&self, field: AssetField) -> bool {
+        self.fields
+            .as_ref()
+            .map_or(true, |fields| fields.contains(&field))
+    }
+}
+
+/// Sections of an asset, named like in the response.  `id`, `interface`, `ownership`, `mutable`
+/// and `burnt` are read from the asset itself and always returned.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetField {
+    Id,
+    Interface,
+    Content,
+    Authorities,
+    Compression,
+    Grouping,
+    Royalty,
+    Creators,
+    Ownership,
+    Uses,
+    Supply,
+    Mutable,
+    Burnt,
+    TokenInfo,
 }
@@ -1,9 +1,24 @@
 use schemars::JsonSchema;
 use {
-    crate::rpc::Asset,
+    crate::dao::sea_orm_active_enums::AssetEventType,
+    crate::rpc::{Asset, TokenAccount},
     serde::{Deserialize, Serialize},
 };
 
+/// Freshness of the index a response was served from.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default, JsonSchema)]
+pub struct RpcContext {
+    /// Slot up to which every ingester stream has been processed.
+    pub slot: u64,
+}
+
+/// A method result along with the context it was read in, like Solana RPC responses.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RpcResponse<T> {
+    pub context: RpcContext,
+    pub value: T,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
 #[serde(default)]
 pub struct AssetError {
@@ -19,10 +34,87 @@ pub struct GetGroupingResponse {
     pub group_size: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct CollectionHolderList {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub items: Vec<CollectionHolder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CollectionHolder {
+    pub owner: String,
+    /// Number of the collection's assets held by the owner.
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+#[serde(default)]
+pub struct CollectionStats {
+    pub collection: String,
+    /// Name and image of the collection NFT, when its metadata has been indexed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Verified members, burnt ones included.
+    pub group_size: u64,
+    /// Distinct owners of the verified members that are not burnt.
+    pub unique_holders: u64,
+    pub burnt: u64,
+    pub compressed: u64,
+    pub uncompressed: u64,
+    /// Assets that name the collection without having been verified into it.
+    pub unverified: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_mint_slot: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_mint_slot: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+#[serde(default)]
+pub struct OwnerCollections {
+    pub owner: String,
+    /// Number of assets held across all collections, not only the ones on this page.
+    pub total: u64,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    pub items: Vec<OwnerCollection>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct OwnerCollection {
+    /// Absent for the bucket of assets that belong to no collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    pub verified: bool,
+    pub count: u64,
+    /// One of the held assets, for previews.
+    pub sample_asset: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
 #[serde(default)]
 pub struct AssetList {
     pub total: u32,
+    /// Number of assets matching the query across all pages, when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grand_total: Option<u64>,
     pub limit: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<u32>,
@@ -48,5 +140,201 @@ pub struct TransactionSignatureList {
     pub before: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<String>,
-    pub items: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub items: Vec<TransactionSignature>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+#[serde(default)]
+pub struct TransactionSignature {
+    pub signature: String,
+    /// Bubblegum instruction of the transaction, only set for compressed assets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction: Option<String>,
+    /// Slot of the transaction, unknown for transactions indexed before it was recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Tree sequence number of the change made to the asset's leaf, only set for compressed
+    /// assets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct AssetHistory {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub items: Vec<AssetEvent>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub enum AssetEventKind {
+    Mint,
+    Transfer,
+    Delegate,
+    Undelegate,
+    Burn,
+    Redeem,
+    CancelRedeem,
+    VerifyCollection,
+    UnverifyCollection,
+    VerifyCreator,
+    UnverifyCreator,
+    UpdateMetadata,
+}
+
+impl From<AssetEventType> for AssetEventKind {
+    fn from(event_type: AssetEventType) -> Self {
+        match event_type {
+            AssetEventType::Mint => AssetEventKind::Mint,
+            AssetEventType::Transfer => AssetEventKind::Transfer,
+            AssetEventType::Delegate => AssetEventKind::Delegate,
+            AssetEventType::Undelegate => AssetEventKind::Undelegate,
+            AssetEventType::Burn => AssetEventKind::Burn,
+            AssetEventType::Redeem => AssetEventKind::Redeem,
+            AssetEventType::CancelRedeem => AssetEventKind::CancelRedeem,
+            AssetEventType::VerifyCollection => AssetEventKind::VerifyCollection,
+            AssetEventType::UnverifyCollection => AssetEventKind::UnverifyCollection,
+            AssetEventType::VerifyCreator => AssetEventKind::VerifyCreator,
+            AssetEventType::UnverifyCreator => AssetEventKind::UnverifyCreator,
+            AssetEventType::UpdateMetadata => AssetEventKind::UpdateMetadata,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AssetEvent {
+    pub event: AssetEventKind,
+    pub signature: String,
+    pub slot: u64,
+    /// Tree sequence number of the change made to the asset's leaf.
+    pub seq: u64,
+    /// Previous owner on transfers, burns and redeems, previous delegate on delegations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// New owner on mints, transfers and cancelled redeems, new delegate on delegations, and
+    /// the creator or collection being verified or unverified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct TokenAccountList {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub token_accounts: Vec<TokenAccount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+pub struct TreeInfo {
+    pub tree_id: String,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    /// Unknown until the tree account has been indexed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canopy_depth: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree_config: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree_creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree_delegate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_public: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_minted: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_mint_capacity: Option<u64>,
+    /// Latest sequence number read from the tree account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_chain_seq: Option<u64>,
+    /// Latest sequence number whose change has been indexed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_seq: Option<u64>,
+    /// Ranges of sequence numbers below `indexed_seq` missing from the change history of the tree.
+    pub gaps: Vec<SeqGap>,
+}
+
+/// Inclusive range of missing sequence numbers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SeqGap {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// How far behind the ingester streams are, which is all `/health` looks at.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+pub struct IndexerHealth {
+    /// Whether every stream is within the configured lag and backlog thresholds.
+    pub healthy: bool,
+    /// Slot up to which every stream has been processed, as reported in response contexts.
+    pub context_slot: u64,
+    pub streams: Vec<StreamStatus>,
+    /// Read replicas of the api, as of their last check.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replicas: Vec<ReplicaStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ReplicaStatus {
+    pub reachable: bool,
+    /// Slot the replica has been processed up to.
+    pub slot: u64,
+    /// Slots the replica trails the primary by.
+    pub lag_slots: u64,
+    /// Whether reads are routed to the replica, rather than falling back to the primary.
+    pub serving_reads: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+pub struct IndexerStatus {
+    #[serde(flatten)]
+    pub health: IndexerHealth,
+    pub tasks: TaskCounts,
+    pub backfill: BackfillStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct StreamStatus {
+    pub stream: String,
+    /// Latest slot the stream has been processed up to.
+    pub slot: u64,
+    /// Slots the stream trails the newest slot processed by any stream by.
+    pub lag_slots: u64,
+    /// Seconds since the stream last made progress.
+    pub lag_seconds: u64,
+    /// Messages waiting in the stream, when last measured by the ingester.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backlog: Option<u64>,
+}
+
+/// Background tasks, such as metadata downloads.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+pub struct TaskCounts {
+    pub pending: u64,
+    pub failed: u64,
+}
+
+/// Trees with gaps in their indexed history.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default, JsonSchema)]
+pub struct BackfillStatus {
+    /// Trees waiting to be checked or backfilled.
+    pub pending_trees: u64,
+    /// Trees the backfiller gave up on.
+    pub failed_trees: u64,
 }
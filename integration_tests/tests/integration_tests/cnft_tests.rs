@@ -2,12 +2,33 @@ use function_name::named;
 use std::str::FromStr;
 
 use das_api::api::{self, ApiContract};
+use das_api::builder::RpcApiBuilder;
+
+use digital_asset_types::dao::Cursor;
+use digital_asset_types::rpc::{options::Options, Asset};
 
 use itertools::Itertools;
 
+use jsonrpsee::core::params::ObjectParams;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use mpl_bubblegum::{accounts::TreeConfig, types::DecompressibleState};
+
+use nft_ingester::program_transformers::save_merkle_tree_header;
+use nft_ingester::tree_history::{backfill_tree_history, prune_tree_history};
+
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+
 use serial_test::serial;
 
-use solana_sdk::signature::Signature;
+use solana_sdk::{hash::hashv, pubkey::Pubkey, signature::Signature};
+
+use std::time::Duration;
+
+use spl_account_compression::state::{
+    merkle_tree_get_size, ConcurrentMerkleTreeHeader, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
 
 use super::common::*;
 
@@ -348,3 +369,1193 @@ async fn test_mint_verify_collection() {
 
     run_get_asset_scenario_test(&setup, asset_id, seeds, Order::AllPermutations).await;
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_proof_history() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "EcLv3bbLYr2iH5PVEuf9pJMRdDCvCqwSx3Srz6AeKjAe";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let latest = setup
+        .das_api
+        .get_asset_proof(api::GetAssetProof {
+            id: asset_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let seqs: Vec<i64> = setup
+        .db
+        .query_all(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT DISTINCT seq FROM cl_items_history ORDER BY seq".to_string(),
+        ))
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.try_get("", "seq").unwrap())
+        .collect();
+
+    let mut proofs = Vec::new();
+    for seq in &seqs {
+        let by_seq = setup
+            .das_api
+            .get_asset_proof(api::GetAssetProof {
+                id: asset_id.to_string(),
+                seq: Some(*seq as u64),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let by_root = setup
+            .das_api
+            .get_asset_proof(api::GetAssetProof {
+                id: asset_id.to_string(),
+                root: Some(by_seq.root.clone()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(by_seq, by_root);
+        proofs.push(by_seq);
+    }
+    assert_eq!(proofs.last(), Some(&latest));
+    assert_ne!(proofs.first(), Some(&latest));
+
+    let batch = setup
+        .das_api
+        .get_asset_proofs(api::GetAssetProofs {
+            ids: vec![asset_id.to_string()],
+            seq: Some(seqs[0] as u64),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        batch.get(asset_id).cloned().flatten().as_ref(),
+        proofs.first()
+    );
+    let missing_id = "11111111111111111111111111111111";
+    let batch = setup
+        .das_api
+        .get_asset_proofs(api::GetAssetProofs {
+            ids: vec![asset_id.to_string(), missing_id.to_string()],
+            root: proofs.first().map(|proof| proof.root.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        batch.get(asset_id).cloned().flatten().as_ref(),
+        proofs.first()
+    );
+    assert_eq!(batch.get(missing_id), Some(&None));
+
+    // Once the history is pruned down to the latest change, proofs before it can't be rebuilt.
+    let pruned = prune_tree_history(setup.db.as_ref(), 0).await.unwrap();
+    assert!(pruned > 0);
+    for (seq, proof) in seqs.iter().zip(&proofs) {
+        let by_seq = setup
+            .das_api
+            .get_asset_proof(api::GetAssetProof {
+                id: asset_id.to_string(),
+                seq: Some(*seq as u64),
+                ..Default::default()
+            })
+            .await;
+        if proof == &latest {
+            assert_eq!(by_seq.unwrap(), latest);
+        } else {
+            assert!(by_seq.is_err());
+        }
+    }
+
+    insta::assert_json_snapshot!(name, proofs);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_tree_history_backfill() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "EcLv3bbLYr2iH5PVEuf9pJMRdDCvCqwSx3Srz6AeKjAe";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let latest = setup
+        .das_api
+        .get_asset_proof(api::GetAssetProof {
+            id: asset_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // A tree indexed before the history was kept only has its current nodes to start from.
+    setup
+        .db
+        .execute(Statement::from_string(
+            DbBackend::Postgres,
+            "DELETE FROM cl_items_history".to_string(),
+        ))
+        .await
+        .unwrap();
+    let copied = backfill_tree_history(setup.db.as_ref()).await.unwrap();
+    assert!(copied > 0);
+    // Running it again copies nothing new.
+    assert_eq!(backfill_tree_history(setup.db.as_ref()).await.unwrap(), 0);
+
+    let by_root = setup
+        .das_api
+        .get_asset_proof(api::GetAssetProof {
+            id: asset_id.to_string(),
+            root: Some(latest.root.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(by_root, latest);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_proof_truncate_canopy() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "EcLv3bbLYr2iH5PVEuf9pJMRdDCvCqwSx3Srz6AeKjAe";
+    let tree_id = Pubkey::from_str("AdKerrR54h9H1zNoyex9f6svuQQSeKrSEufiGtA5zu8v").unwrap();
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    // A depth 14 tree with a canopy of depth 3 (2^4 - 2 nodes).
+    let mut header =
+        ConcurrentMerkleTreeHeader::try_from_slice(&[0; CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1])
+            .unwrap();
+    header.initialize(14, 64, &Pubkey::default(), 0);
+    let mut data = header.try_to_vec().unwrap();
+    data.resize(
+        CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + merkle_tree_get_size(&header).unwrap() + 14 * 32,
+        0,
+    );
+    save_merkle_tree_header(setup.db.as_ref(), tree_id.as_ref(), &data, 1)
+        .await
+        .unwrap();
+
+    let full = setup
+        .das_api
+        .get_asset_proof(api::GetAssetProof {
+            id: asset_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let truncated = setup
+        .das_api
+        .get_asset_proofs(api::GetAssetProofs {
+            ids: vec![asset_id.to_string()],
+            truncate_canopy: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    insta::assert_json_snapshot!(name, (full, truncated));
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_signatures_instruction_filter() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "EcLv3bbLYr2iH5PVEuf9pJMRdDCvCqwSx3Srz6AeKjAe";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let all = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(asset_id.to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(all.items.len(), 3);
+
+    let first_transfer = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(asset_id.to_string()),
+            instruction: Some("Transfer".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let second_transfer = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(asset_id.to_string()),
+            instruction: Some("Transfer".to_string()),
+            limit: Some(1),
+            cursor: first_transfer.cursor.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        [first_transfer.items.clone(), second_transfer.items.clone()].concat(),
+        all.items[..2]
+    );
+
+    let invalid = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(asset_id.to_string()),
+            instruction: Some("Teleport".to_string()),
+            ..Default::default()
+        })
+        .await;
+    assert!(invalid.is_err());
+
+    // Compressed signatures are ordered by sequence number, which a pubkey can't bound.
+    let keyset = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            tree: Some(Pubkey::new_unique().to_string()),
+            leaf_index: Some(0),
+            before: Some(Pubkey::new_unique().to_string()),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        keyset,
+        Err(das_api::error::DasApiError::PaginationKeysetUnsupportedError)
+    ));
+
+    insta::assert_json_snapshot!(name, (all, first_transfer, second_transfer));
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_history() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "77wWrvhgEkkQZQVA2hoka1JTsjG3w7BVzvcmqxDrVPWE";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "KNWsAYPo3mm1HuFxRyEwBBMUZ2hqTnFXjoPVFo7WxGTfmfRwz6K8eERc4dnJpHyuoDkAZu1czK55iB1SbtCsdW2",
+        "3B1sASkuToCWuGFRG47axQDm1SpgLi8qDDGnRFeR7LB6oa5C3ZmkEuX98373gdMTBXED44FkwT227kBBAGSw7e8M",
+        "5Q8TAMMkMTHEM2BHyD2fp2sVdYKByFeATzM2mHF6Xbbar33WaeuygPKGYCWiDEt3MZU1mUrq1ePnT9o4Pa318p8w",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let history = setup
+        .das_api
+        .get_asset_history(api::GetAssetHistory {
+            id: asset_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(history.items.len(), 3);
+
+    let first_page = setup
+        .das_api
+        .get_asset_history(api::GetAssetHistory {
+            id: asset_id.to_string(),
+            limit: Some(2),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let second_page = setup
+        .das_api
+        .get_asset_history(api::GetAssetHistory {
+            id: asset_id.to_string(),
+            limit: Some(2),
+            cursor: first_page.cursor.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        [first_page.items.clone(), second_page.items.clone()].concat(),
+        history.items
+    );
+
+    insta::assert_json_snapshot!(name, history);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_tree_info() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let tree_id = Pubkey::from_str("AdKerrR54h9H1zNoyex9f6svuQQSeKrSEufiGtA5zu8v").unwrap();
+    let (tree_config, _) = TreeConfig::find_pda(&tree_id);
+    let tree_creator = Pubkey::from_str("Fq4HDXfutKjEZ7zZP2JmKboSm2ZsYsKEJ7BLQAfrpNcc").unwrap();
+    let tree_delegate = Pubkey::new_from_array([2; 32]);
+
+    // The mint transaction also creates the tree.
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let created = setup
+        .das_api
+        .get_tree_info(api::GetTreeInfo {
+            id: tree_id.to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.tree_creator, Some(tree_creator.to_string()));
+    assert_eq!(created.num_minted, Some(0));
+    assert_eq!(created.canopy_depth, None);
+    assert_eq!(created.on_chain_seq, None);
+    assert_eq!(created.indexed_seq, Some(3));
+
+    // The tree account at seq 5, with a canopy of depth 3 (2^4 - 2 nodes).
+    let mut header =
+        ConcurrentMerkleTreeHeader::try_from_slice(&[0; CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1])
+            .unwrap();
+    header.initialize(14, 64, &tree_config, 0);
+    let mut data = header.try_to_vec().unwrap();
+    data.extend_from_slice(&5u64.to_le_bytes());
+    data.resize(
+        CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + merkle_tree_get_size(&header).unwrap() + 14 * 32,
+        0,
+    );
+    save_merkle_tree_header(setup.db.as_ref(), tree_id.as_ref(), &data, 224501200)
+        .await
+        .unwrap();
+
+    let mut config = TreeConfig {
+        discriminator: [0; 8],
+        tree_creator,
+        tree_delegate,
+        total_mint_capacity: 1 << 14,
+        num_minted: 1,
+        is_public: true,
+        is_decompressible: DecompressibleState::Disabled,
+    };
+    config
+        .discriminator
+        .copy_from_slice(&hashv(&[b"account:TreeConfig"]).to_bytes()[..8]);
+    let mut config_data = config.try_to_vec().unwrap();
+    config_data.resize(TreeConfig::LEN, 0);
+    index_account_bytes(
+        &setup,
+        serialize_local_account(224501200, tree_config, mpl_bubblegum::ID, &config_data),
+    )
+    .await;
+
+    // Drop the change at seq 2 from the history to open a gap.
+    setup
+        .db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM cl_items_history WHERE tree = $1 AND seq = 2",
+            vec![tree_id.to_bytes().to_vec().into()],
+        ))
+        .await
+        .unwrap();
+
+    let tree_info = setup
+        .das_api
+        .get_tree_info(api::GetTreeInfo {
+            id: tree_id.to_string(),
+        })
+        .await
+        .unwrap();
+
+    insta::assert_json_snapshot!(name, tree_info);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_assets_by_tree() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let tree_id = "AdKerrR54h9H1zNoyex9f6svuQQSeKrSEufiGtA5zu8v";
+
+    // Assets from two different trees.
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+        "KNWsAYPo3mm1HuFxRyEwBBMUZ2hqTnFXjoPVFo7WxGTfmfRwz6K8eERc4dnJpHyuoDkAZu1czK55iB1SbtCsdW2",
+        "3B1sASkuToCWuGFRG47axQDm1SpgLi8qDDGnRFeR7LB6oa5C3ZmkEuX98373gdMTBXED44FkwT227kBBAGSw7e8M",
+        "5Q8TAMMkMTHEM2BHyD2fp2sVdYKByFeATzM2mHF6Xbbar33WaeuygPKGYCWiDEt3MZU1mUrq1ePnT9o4Pa318p8w",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let all = setup
+        .das_api
+        .get_assets_by_tree(api::GetAssetsByTree {
+            tree: tree_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(all.items.len(), 1);
+    let leaf_id = all.items[0].compression.as_ref().unwrap().leaf_id as u64;
+
+    let in_range = setup
+        .das_api
+        .get_assets_by_tree(api::GetAssetsByTree {
+            tree: tree_id.to_string(),
+            from_leaf: Some(leaf_id),
+            to_leaf: Some(leaf_id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(in_range.items, all.items);
+
+    let out_of_range = setup
+        .das_api
+        .get_assets_by_tree(api::GetAssetsByTree {
+            tree: tree_id.to_string(),
+            from_leaf: Some(leaf_id + 1),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(out_of_range.items.is_empty());
+
+    let next_page = setup
+        .das_api
+        .get_assets_by_tree(api::GetAssetsByTree {
+            tree: tree_id.to_string(),
+            limit: Some(1),
+            cursor: all.cursor.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(next_page.items.is_empty());
+
+    let bad_cursor = setup
+        .das_api
+        .get_assets_by_tree(api::GetAssetsByTree {
+            tree: tree_id.to_string(),
+            cursor: Cursor {
+                id: Some(vec![1, 2, 3]),
+                sort_value: None,
+            }
+            .encode(),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        bad_cursor,
+        Err(das_api::error::DasApiError::PaginationError)
+    ));
+
+    let invalid_range = setup
+        .das_api
+        .get_assets_by_tree(api::GetAssetsByTree {
+            tree: tree_id.to_string(),
+            from_leaf: Some(2),
+            to_leaf: Some(1),
+            ..Default::default()
+        })
+        .await;
+    assert!(invalid_range.is_err());
+
+    insta::assert_json_snapshot!(name, all);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_assets_by_delegate() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let delegate = "8C6rKrjuDpuDcKrbjYbFGUahthiSzrmqHraS7LrEqyhS";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "KNWsAYPo3mm1HuFxRyEwBBMUZ2hqTnFXjoPVFo7WxGTfmfRwz6K8eERc4dnJpHyuoDkAZu1czK55iB1SbtCsdW2",
+        "3B1sASkuToCWuGFRG47axQDm1SpgLi8qDDGnRFeR7LB6oa5C3ZmkEuX98373gdMTBXED44FkwT227kBBAGSw7e8M",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let response = setup
+        .das_api
+        .get_assets_by_delegate(api::GetAssetsByDelegate {
+            delegate_address: delegate.to_string(),
+            sort_by: None,
+            limit: None,
+            page: Some(1),
+            before: None,
+            after: None,
+            options: Some(Options {
+                show_grand_total: true,
+                ..Default::default()
+            }),
+            cursor: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.grand_total, Some(1));
+
+    insta::assert_json_snapshot!(name, response);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_collection_holders() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let collection = "4zapNXifB7Lz5XGUtsYQ3gsEujK2dqFw4mE9NY57NrtD";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "63xhs5bXcuMR3uMACXWkkFMm7BJ9Thknh7WNMPzV8HJBNwpyxJTr98NrLFHnTZDHdSUFD42VFQx8rjSaGynWbaRs",
+        "5ZKjPxm3WAZzuqqkCDjgKpm9b5XjB9cuvv68JvXxWThvJaJxcMJgpSbYs4gDA9dGJyeLzsgNtnS6oubANF1KbBmt",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let holders = setup
+        .das_api
+        .get_collection_holders(api::GetCollectionHolders {
+            group_value: collection.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(holders.items.len(), 1);
+
+    let next_page = setup
+        .das_api
+        .get_collection_holders(api::GetCollectionHolders {
+            group_value: collection.to_string(),
+            cursor: holders.cursor.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(next_page.items.is_empty());
+
+    // Burnt assets no longer count towards their owner.
+    setup
+        .db
+        .execute(Statement::from_string(
+            DbBackend::Postgres,
+            "UPDATE asset SET burnt = true".to_string(),
+        ))
+        .await
+        .unwrap();
+    let after_burn = setup
+        .das_api
+        .get_collection_holders(api::GetCollectionHolders {
+            group_value: collection.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(after_burn.items.is_empty());
+
+    insta::assert_json_snapshot!(name, holders);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_collection_stats() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let collection = "4zapNXifB7Lz5XGUtsYQ3gsEujK2dqFw4mE9NY57NrtD";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "63xhs5bXcuMR3uMACXWkkFMm7BJ9Thknh7WNMPzV8HJBNwpyxJTr98NrLFHnTZDHdSUFD42VFQx8rjSaGynWbaRs",
+        "5ZKjPxm3WAZzuqqkCDjgKpm9b5XjB9cuvv68JvXxWThvJaJxcMJgpSbYs4gDA9dGJyeLzsgNtnS6oubANF1KbBmt",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let stats = setup
+        .das_api
+        .get_collection_stats(api::GetCollectionStats {
+            group_value: collection.to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(stats.group_size, 1);
+    assert_eq!(stats.unique_holders, 1);
+    assert_eq!(stats.compressed, 1);
+    assert_eq!(stats.uncompressed, 0);
+    assert!(stats.first_mint_slot.is_some());
+
+    // The collection NFT itself is not indexed, so the grouping keeps its address as its name.
+    let grouping = setup
+        .das_api
+        .get_grouping(api::GetGrouping {
+            group_key: "collection".to_string(),
+            group_value: collection.to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(grouping.group_size, stats.group_size);
+    assert_eq!(grouping.group_name, collection);
+
+    insta::assert_json_snapshot!(name, stats);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_owner_collections() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let owner = "Fq4HDXfutKjEZ7zZP2JmKboSm2ZsYsKEJ7BLQAfrpNcc";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "63xhs5bXcuMR3uMACXWkkFMm7BJ9Thknh7WNMPzV8HJBNwpyxJTr98NrLFHnTZDHdSUFD42VFQx8rjSaGynWbaRs",
+        "5ZKjPxm3WAZzuqqkCDjgKpm9b5XjB9cuvv68JvXxWThvJaJxcMJgpSbYs4gDA9dGJyeLzsgNtnS6oubANF1KbBmt",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let collections = setup
+        .das_api
+        .get_owner_collections(api::GetOwnerCollections {
+            owner_address: owner.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(collections.total, 1);
+    assert_eq!(collections.items.len(), 1);
+    assert!(collections.items[0].verified);
+
+    // The total covers every page.
+    let second_page = setup
+        .das_api
+        .get_owner_collections(api::GetOwnerCollections {
+            owner_address: owner.to_string(),
+            limit: Some(1),
+            page: Some(2),
+        })
+        .await
+        .unwrap();
+    assert_eq!(second_page.total, 1);
+    assert!(second_page.items.is_empty());
+    assert!(setup
+        .das_api
+        .get_owner_collections(api::GetOwnerCollections {
+            owner_address: owner.to_string(),
+            limit: Some(1001),
+            ..Default::default()
+        })
+        .await
+        .is_err());
+
+    // Without its grouping the asset falls into the uncollected bucket.
+    setup
+        .db
+        .execute(Statement::from_string(
+            DbBackend::Postgres,
+            "DELETE FROM asset_grouping".to_string(),
+        ))
+        .await
+        .unwrap();
+    let uncollected = setup
+        .das_api
+        .get_owner_collections(api::GetOwnerCollections {
+            owner_address: owner.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(uncollected.items.len(), 1);
+    assert_eq!(uncollected.items[0].collection, None);
+    assert!(!uncollected.items[0].verified);
+
+    insta::assert_json_snapshot!(name, collections);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_assets_by_owners_and_creators() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+        "KNWsAYPo3mm1HuFxRyEwBBMUZ2hqTnFXjoPVFo7WxGTfmfRwz6K8eERc4dnJpHyuoDkAZu1czK55iB1SbtCsdW2",
+        "3B1sASkuToCWuGFRG47axQDm1SpgLi8qDDGnRFeR7LB6oa5C3ZmkEuX98373gdMTBXED44FkwT227kBBAGSw7e8M",
+        "5Q8TAMMkMTHEM2BHyD2fp2sVdYKByFeATzM2mHF6Xbbar33WaeuygPKGYCWiDEt3MZU1mUrq1ePnT9o4Pa318p8w",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let owners = [
+        "8WUbLiKgdqA2jcA8xR2Qm1nxeQTHSiz2HTyZDb7nZu6n",
+        "DjpoGceP1EJa2qEaZq8kS1GHTeDDL3AXJcSwLtrwqNj3",
+    ];
+
+    // Page through the owners' assets one at a time.
+    let mut ids = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let request: api::GetAssetsByOwner = serde_json::from_value(serde_json::json!({
+            "ownerAddresses": owners,
+            "limit": 1,
+            "cursor": cursor,
+        }))
+        .unwrap();
+        let response = setup.das_api.get_assets_by_owner(request).await.unwrap();
+        if response.items.is_empty() {
+            break;
+        }
+        ids.extend(response.items.into_iter().map(|asset| asset.id));
+        cursor = response.cursor;
+    }
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids.iter().unique().count(), 2);
+
+    let request: api::GetAssetsByOwner = serde_json::from_value(serde_json::json!({
+        "ownerAddress": owners[0],
+        "ownerAddresses": owners,
+    }))
+    .unwrap();
+    assert!(setup.das_api.get_assets_by_owner(request).await.is_err());
+
+    // An asset listing several of the creators is returned once.
+    let request: api::GetAssetsByCreator = serde_json::from_value(serde_json::json!({
+        "creatorAddresses": [
+            "Fq4HDXfutKjEZ7zZP2JmKboSm2ZsYsKEJ7BLQAfrpNcc",
+            "AhKyxn99oZN7MhdWfU8S9qmmBZBkQYgr2KaNQEJWVHcd",
+        ],
+        "options": { "showGrandTotal": true },
+    }))
+    .unwrap();
+    let by_creators = setup.das_api.get_assets_by_creator(request).await.unwrap();
+    assert_eq!(by_creators.items.len(), 2);
+    assert_eq!(by_creators.grand_total, Some(2));
+
+    let search = setup
+        .das_api
+        .search_assets(api::SearchAssets {
+            owner_addresses: Some(owners.iter().map(|o| o.to_string()).collect()),
+            creator_addresses: Some(vec![
+                "Fq4HDXfutKjEZ7zZP2JmKboSm2ZsYsKEJ7BLQAfrpNcc".to_string(),
+                "AhKyxn99oZN7MhdWfU8S9qmmBZBkQYgr2KaNQEJWVHcd".to_string(),
+            ]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(search.items.len(), 2);
+
+    insta::assert_json_snapshot!(name, by_creators);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_assets_by_removed_creator() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "Gi4fAXJdnWYrEPjQm3wnW9ctgG7zJjB67zHDQtRGRWyZ";
+    let mint = seed_txn(
+        "2qMQrXfRE7pdnjwobWeqDkEhsv6MYmv3JdgvNxTVaL1VrMCZ4JYkUnu7jiJb2etX3W9WyQgSxktUgn9skxCeqTo5",
+    );
+    // Updates the metadata to have no creators.
+    let update = seed_txn(
+        "41YW187sn6Z2dXfqz6zSbnPtQoE826cCSgTLnMLKa9rH1xrCqAXBQNwKnzjGc9wjU5RtMCqKhy2eMN2TjuYC8veB",
+    );
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, vec![&mint]).await;
+    let asset = setup
+        .das_api
+        .get_asset(api::GetAsset {
+            id: asset_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let creator = asset.creators.unwrap()[0].address.clone();
+
+    // The creators written before the update are stale in either order.
+    for events in [vec![&mint, &update], vec![&update, &mint]] {
+        apply_migrations_and_delete_data(setup.db.clone()).await;
+        index_seed_events(&setup, events).await;
+
+        let request: api::GetAssetsByCreator = serde_json::from_value(serde_json::json!({
+            "creatorAddress": creator,
+        }))
+        .unwrap();
+        let by_creator = setup.das_api.get_assets_by_creator(request).await.unwrap();
+        assert!(by_creator.items.is_empty());
+
+        for creator_verified in [true, false] {
+            let search = setup
+                .das_api
+                .search_assets(api::SearchAssets {
+                    creator_verified: Some(creator_verified),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            assert!(search.items.is_empty());
+        }
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_search_assets_filter_tree() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5bq936UgGs4RnxM78iXp1PwVhr8sTYoEsHCWpr8QBFtc2YtS3ieYHcsPG46G2ikwrS3tXYnUK93PzseT52AR81RR",
+        "5VC3Jqr5X1N8NB8zuSahHpayekLVozYkDiPjJLqU6H5M6fq9ExVLGYYKKCPbeksMPXTjy65sdEQGPzDWAYPs8QjP",
+        "34xjcNf3rZFKz381hKpFLqxpojaDgXEpCqH5qcpTXLaJnDbtqRz35wiuMF1cAgvJGLzYYrwaMvCK1D7LxYsdpMU1",
+        "KNWsAYPo3mm1HuFxRyEwBBMUZ2hqTnFXjoPVFo7WxGTfmfRwz6K8eERc4dnJpHyuoDkAZu1czK55iB1SbtCsdW2",
+        "3B1sASkuToCWuGFRG47axQDm1SpgLi8qDDGnRFeR7LB6oa5C3ZmkEuX98373gdMTBXED44FkwT227kBBAGSw7e8M",
+        "5Q8TAMMkMTHEM2BHyD2fp2sVdYKByFeATzM2mHF6Xbbar33WaeuygPKGYCWiDEt3MZU1mUrq1ePnT9o4Pa318p8w",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let first_owner = "8WUbLiKgdqA2jcA8xR2Qm1nxeQTHSiz2HTyZDb7nZu6n";
+    let second_owner = "DjpoGceP1EJa2qEaZq8kS1GHTeDDL3AXJcSwLtrwqNj3";
+
+    let request: api::SearchAssets = serde_json::from_value(serde_json::json!({
+        "filter": {
+            "all": [
+                { "any": [{ "ownerAddress": first_owner }, { "ownerAddress": second_owner }] },
+                { "not": { "ownerAddress": first_owner } },
+                { "not": { "burnt": true } },
+            ]
+        }
+    }))
+    .unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.items.len(), 1);
+    assert_eq!(response.items[0].ownership.owner, second_owner.to_string());
+
+    // Metadata conditions join the asset data, also when they sit under a group.
+    let request: api::SearchAssets = serde_json::from_value(serde_json::json!({
+        "filter": {
+            "all": [
+                { "any": [{ "name": "test" }, { "symbol": "does not exist" }] },
+                { "not": { "ownerAddress": second_owner } },
+            ]
+        }
+    }))
+    .unwrap();
+    let by_name = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(by_name.items.len(), 1);
+    assert_eq!(by_name.items[0].ownership.owner, first_owner.to_string());
+
+    // Negated conditions still match assets without a value for them, like these compressed
+    // assets without a delegate, supply mint or royalty target.
+    let owned: api::SearchAssets = serde_json::from_value(serde_json::json!({
+        "filter": { "any": [{ "ownerAddress": first_owner }, { "ownerAddress": second_owner }] }
+    }))
+    .unwrap();
+    let owned = setup.das_api.search_assets(owned).await.unwrap();
+    let request: api::SearchAssets = serde_json::from_value(serde_json::json!({
+        "filter": {
+            "all": [
+                { "any": [{ "ownerAddress": first_owner }, { "ownerAddress": second_owner }] },
+                { "not": { "delegate": first_owner } },
+                { "not": { "supplyMint": first_owner } },
+                { "not": { "royaltyTarget": first_owner } },
+            ]
+        }
+    }))
+    .unwrap();
+    let negated = setup.das_api.search_assets(request).await.unwrap();
+    assert!(!owned.items.is_empty());
+    assert_eq!(negated.items, owned.items);
+
+    let mut nested = serde_json::json!({ "burnt": false });
+    for _ in 0..10 {
+        nested = serde_json::json!({ "not": nested });
+    }
+    let request: api::SearchAssets =
+        serde_json::from_value(serde_json::json!({ "filter": nested })).unwrap();
+    assert!(setup.das_api.search_assets(request).await.is_err());
+
+    let request: api::SearchAssets =
+        serde_json::from_value(serde_json::json!({ "filter": { "any": [] } })).unwrap();
+    assert!(setup.das_api.search_assets(request).await.is_err());
+
+    // Wide filters are capped too, not only deep ones.
+    let wide = |leaves: usize| -> api::SearchAssets {
+        serde_json::from_value(serde_json::json!({
+            "filter": { "any": vec![serde_json::json!({ "burnt": false }); leaves] }
+        }))
+        .unwrap()
+    };
+    assert!(setup.das_api.search_assets(wide(99)).await.is_ok());
+    assert!(setup.das_api.search_assets(wide(100)).await.is_err());
+
+    insta::assert_json_snapshot!(name, response);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_field_projection() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "63xhs5bXcuMR3uMACXWkkFMm7BJ9Thknh7WNMPzV8HJBNwpyxJTr98NrLFHnTZDHdSUFD42VFQx8rjSaGynWbaRs",
+        "5ZKjPxm3WAZzuqqkCDjgKpm9b5XjB9cuvv68JvXxWThvJaJxcMJgpSbYs4gDA9dGJyeLzsgNtnS6oubANF1KbBmt",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let request: api::GetAssetsByOwner = serde_json::from_value(serde_json::json!({
+        "ownerAddress": "Fq4HDXfutKjEZ7zZP2JmKboSm2ZsYsKEJ7BLQAfrpNcc",
+        "options": { "fields": ["id", "ownership", "grouping"] },
+    }))
+    .unwrap();
+    let response = setup.das_api.get_assets_by_owner(request).await.unwrap();
+    assert_eq!(response.items.len(), 1);
+    let asset = &response.items[0];
+    assert!(asset.grouping.is_some());
+    assert!(asset.content.is_none());
+    assert!(asset.creators.is_none());
+    assert!(asset.authorities.is_none());
+
+    let request: api::GetAsset = serde_json::from_value(serde_json::json!({
+        "id": "2WjoMU1hBGXv8sKcxQDGnu1tgMduzdZEmEEGjh8MZYfC",
+        "options": { "fields": ["creators"] },
+    }))
+    .unwrap();
+    let asset = setup.das_api.get_asset(request).await.unwrap();
+    assert!(asset.creators.is_some_and(|creators| !creators.is_empty()));
+    assert!(asset.grouping.is_none());
+
+    let request: Result<api::GetAsset, _> = serde_json::from_value(serde_json::json!({
+        "id": "2WjoMU1hBGXv8sKcxQDGnu1tgMduzdZEmEEGjh8MZYfC",
+        "options": { "fields": ["not_a_field"] },
+    }));
+    assert!(request.is_err());
+
+    insta::assert_json_snapshot!(name, response);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_cnft_owner_subscription_transfer_out() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let asset_id = "7myVr8fEG52mZ3jAwgz88iQRWsuzuVR2nfH8n2AXnBxE";
+    let mint = seed_txn(
+        "4nKDSvw2kGpccZWLEPnfdP7J1SEexQFRP3xWc9NBtQ1qQeGu3bu5WnAdpcLbjQ4iyX6BQ5QGF69wevE8ZeeY5poA",
+    );
+    let transfers = seed_txns([
+        "4URwUGBjbsF7UBUYdSC546tnBy7nD67txsso8D9CR9kGLtbbYh9NkGw15tEp16LLasmJX5VQR4Seh8gDjTrtdpoC",
+        "5bNyZfmxLVP9cKc6GjvozExrSt4F1QFt4PP992pQwT8FFHdWsX3ZFNvwurfU2xpDYtQ7qAUxVahGCraXMevRH8p1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, vec![&mint]).await;
+    let minted_to = setup
+        .das_api
+        .get_asset(api::GetAsset {
+            id: asset_id.to_string(),
+            ..api::GetAsset::default()
+        })
+        .await
+        .unwrap()
+        .ownership
+        .owner;
+
+    let das_api = api::DasApi::from_config(das_api::config::Config {
+        database_url: std::env::var("DATABASE_TEST_URL").unwrap(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let module = RpcApiBuilder::build(Box::new(das_api)).unwrap();
+    let mut params = ObjectParams::new();
+    params.insert("ownerAddress", &minted_to).unwrap();
+    let mut subscription = module.subscribe("ownerSubscribe", params).await.unwrap();
+
+    index_seed_events(&setup, transfers.iter().collect_vec()).await;
+
+    // The previous owner is told the asset now belongs to somebody else.
+    let (asset, _) = tokio::time::timeout(Duration::from_secs(10), subscription.next::<Asset>())
+        .await
+        .expect("timed out waiting for an asset update")
+        .unwrap()
+        .unwrap();
+    assert_eq!(asset.id, asset_id);
+    assert_ne!(asset.ownership.owner, minted_to);
+}
@@ -45,11 +45,16 @@ use solana_client::{
     rpc_request::RpcRequest,
     rpc_response::{Response as RpcResponse, RpcTokenAccountBalance},
 };
+use solana_sdk::transaction::Transaction;
 use solana_sdk::{
     account::Account,
     commitment_config::{CommitmentConfig, CommitmentLevel},
 };
-use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use solana_transaction_status::{
+    ConfirmedTransactionWithStatusMeta, EncodedConfirmedTransactionWithStatusMeta,
+    TransactionStatusMeta, TransactionWithStatusMeta, UiTransactionEncoding,
+    VersionedTransactionWithStatusMeta,
+};
 use std::{fmt, time::Duration};
 
 use std::path::PathBuf;
@@ -164,7 +169,7 @@ async fn load_ingest_program_transformer(pool: sqlx::Pool<sqlx::Postgres>) -> Pr
     let mut background_task_manager = TaskManager::new(rand_string(), pool.clone(), vec![]);
     background_task_manager.start_listener(true);
     let bg_task_sender = background_task_manager.get_sender().unwrap();
-    ProgramTransformer::new(pool, bg_task_sender, false)
+    ProgramTransformer::new(pool, bg_task_sender, true, true, true, true)
 }
 
 pub async fn get_transaction(
@@ -227,7 +232,46 @@ pub async fn fetch_and_serialize_transaction(
     Ok(Some(serialized.to_vec()))
 }
 
+/// Serializes `transaction` as if it was confirmed at `slot`, for scenarios that are built
+/// locally instead of replayed from the chain.
+pub fn serialize_local_transaction(slot: u64, transaction: Transaction) -> Vec<u8> {
+    let tx = ConfirmedTransactionWithStatusMeta {
+        slot,
+        tx_with_meta: TransactionWithStatusMeta::Complete(VersionedTransactionWithStatusMeta {
+            transaction: transaction.into(),
+            meta: TransactionStatusMeta {
+                inner_instructions: Some(Vec::new()),
+                ..Default::default()
+            },
+        }),
+        block_time: None,
+    }
+    .encode(UiTransactionEncoding::Base64, Some(0))
+    .unwrap();
+    let fbb = flatbuffers::FlatBufferBuilder::new();
+    let fbb = seralize_encoded_transaction_with_status(fbb, tx).unwrap();
+    fbb.finished_data().to_vec()
+}
+
 // Util functions for accounts
+/// Serializes an account update for accounts that are built locally instead of read from the
+/// chain.
+pub fn serialize_local_account(slot: u64, pubkey: Pubkey, owner: Pubkey, data: &[u8]) -> Vec<u8> {
+    let fbb = flatbuffers::FlatBufferBuilder::new();
+    let account_info = ReplicaAccountInfoV2 {
+        pubkey: &pubkey.to_bytes(),
+        lamports: 1,
+        owner: &owner.to_bytes(),
+        executable: false,
+        rent_epoch: 0,
+        data,
+        write_version: 0,
+        txn_signature: None,
+    };
+    let fbb = serialize_account(fbb, &account_info, slot, false);
+    fbb.finished_data().to_vec()
+}
+
 pub async fn rpc_tx_with_retries<T, E>(
     client: &RpcClient,
     request: RpcRequest,
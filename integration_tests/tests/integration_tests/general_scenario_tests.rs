@@ -2,11 +2,12 @@ use function_name::named;
 use std::str::FromStr;
 
 use das_api::api::{self, ApiContract};
-use digital_asset_types::dao::asset_creators;
+use digital_asset_types::{dao::asset_creators, rpc::response::ReplicaStatus};
 use migration::sea_orm::{ConnectionTrait, EntityTrait};
 
 use mpl_token_metadata::types::Creator;
-use sea_orm::{DbBackend, QueryTrait, Set};
+use nft_ingester::progress::{record_stream_backlog, record_stream_progress};
+use sea_orm::{DbBackend, DbErr, QueryTrait, Set, Statement};
 use serial_test::serial;
 use solana_sdk::pubkey::Pubkey;
 
@@ -99,3 +100,192 @@ async fn test_creators_reordering() {
     let response = setup.das_api.get_asset(request.clone()).await.unwrap();
     insta::assert_json_snapshot!(name, response);
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_indexer_status() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let das_api = api::DasApi::from_config(das_api::config::Config {
+        database_url: std::env::var("DATABASE_TEST_URL").unwrap(),
+        max_indexer_lag_slots: Some(150),
+        max_indexer_lag_seconds: Some(60),
+        max_indexer_backlog: Some(100),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    // Nothing has been ingested yet.
+    let status = das_api.get_indexer_status().await.unwrap();
+    assert!(!status.health.healthy);
+    assert!(das_api.check_health().await.is_err());
+
+    record_stream_progress(setup.db.as_ref(), "ACC", "consumer", 300)
+        .await
+        .unwrap();
+    record_stream_progress(setup.db.as_ref(), "TXN", "consumer", 200)
+        .await
+        .unwrap();
+    record_stream_backlog(setup.db.as_ref(), "TXN", 42)
+        .await
+        .unwrap();
+    // Backlogs of streams without progress are not tracked.
+    record_stream_backlog(setup.db.as_ref(), "SNAPSHOT", 7)
+        .await
+        .unwrap();
+    for sql in [
+        "INSERT INTO tasks (id, task_type, data, status, created_at, max_attempts, attempts) VALUES
+            ('1', 'DownloadMetadata', '{}', 'pending', NOW(), 3, 0),
+            ('2', 'DownloadMetadata', '{}', 'pending', NOW(), 3, 0),
+            ('3', 'DownloadMetadata', '{}', 'failed', NOW(), 3, 3),
+            ('4', 'DownloadMetadata', '{}', 'success', NOW(), 3, 1)",
+        "INSERT INTO backfill_items (tree, seq, slot, force_chk, backfilled, failed, locked) VALUES
+            ('\\x01', 0, 0, true, false, false, false),
+            ('\\x01', 5, 10, false, false, false, false),
+            ('\\x02', 0, 0, true, false, true, false),
+            ('\\x03', 0, 0, true, true, false, false)",
+    ] {
+        setup
+            .db
+            .execute(Statement::from_string(DbBackend::Postgres, sql.to_string()))
+            .await
+            .unwrap();
+    }
+
+    let health = das_api.check_health().await.unwrap();
+    assert!(health.healthy);
+    assert_eq!(health.context_slot, 200);
+    let streams = health
+        .streams
+        .iter()
+        .map(|s| (s.stream.as_str(), s.slot, s.lag_slots, s.backlog))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        streams,
+        vec![("ACC", 300, 0, None), ("TXN", 200, 100, Some(42))]
+    );
+    assert!(health.streams.iter().all(|s| s.lag_seconds <= 60));
+
+    // Only the status counts the background work.
+    let status = das_api.get_indexer_status().await.unwrap();
+    assert_eq!(status.health, health);
+    assert_eq!(serde_json::to_value(&status).unwrap()["healthy"], true);
+    assert_eq!((status.tasks.pending, status.tasks.failed), (2, 1));
+    assert_eq!(
+        (status.backfill.pending_trees, status.backfill.failed_trees),
+        (1, 1)
+    );
+
+    // A stream falling behind on its messages marks the node unhealthy.
+    record_stream_backlog(setup.db.as_ref(), "TXN", 500)
+        .await
+        .unwrap();
+    assert!(!das_api.get_indexer_status().await.unwrap().health.healthy);
+    assert!(das_api.check_health().await.is_err());
+    record_stream_backlog(setup.db.as_ref(), "TXN", 42)
+        .await
+        .unwrap();
+    assert!(das_api.check_health().await.is_ok());
+
+    // So does a stream falling behind the others.
+    record_stream_progress(setup.db.as_ref(), "ACC", "consumer", 500)
+        .await
+        .unwrap();
+    assert!(das_api.check_health().await.is_err());
+    record_stream_progress(setup.db.as_ref(), "TXN", "consumer", 450)
+        .await
+        .unwrap();
+    assert!(das_api.check_health().await.is_ok());
+
+    // And a stream that stopped making progress.
+    setup
+        .db
+        .execute(Statement::from_string(
+            DbBackend::Postgres,
+            "UPDATE ingester_progress SET updated_at = updated_at - INTERVAL '5 minutes' WHERE stream = 'TXN'"
+                .to_string(),
+        ))
+        .await
+        .unwrap();
+    let status = das_api.get_indexer_status().await.unwrap();
+    assert!(!status.health.healthy);
+    assert!(status.health.streams[1].lag_seconds >= 300);
+    assert!(das_api.check_health().await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_read_replica_routing() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    record_stream_progress(setup.db.as_ref(), "ACC", "consumer", 300)
+        .await
+        .unwrap();
+
+    let database_url = std::env::var("DATABASE_TEST_URL").unwrap();
+    let das_api = api::DasApi::from_config(das_api::config::Config {
+        database_url: database_url.clone(),
+        // A replica that is down is skipped rather than failing the api.
+        replica_database_urls: vec![
+            "postgres://postgres@127.0.0.1:1/das".to_string(),
+            database_url,
+        ],
+        max_database_connections: Some(5),
+        statement_timeout_ms: Some(10_000),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let replicas = das_api.check_health().await.unwrap().replicas;
+    assert_eq!(
+        replicas,
+        vec![
+            ReplicaStatus {
+                reachable: false,
+                slot: 0,
+                lag_slots: 300,
+                serving_reads: false,
+            },
+            ReplicaStatus {
+                reachable: true,
+                slot: 300,
+                lag_slots: 0,
+                serving_reads: true,
+            },
+        ]
+    );
+
+    let (context, reader) = das_api.reader(None).await.unwrap();
+    assert_eq!(context.slot, 300);
+    let err = reader
+        .get_asset(api::GetAsset {
+            id: Pubkey::new_unique().to_string(),
+            ..api::GetAsset::default()
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        das_api::error::DasApiError::DatabaseError(DbErr::RecordNotFound(_))
+    ));
+
+    // The replica was last seen at slot 300, so fresher reads go to the primary.
+    record_stream_progress(setup.db.as_ref(), "ACC", "consumer", 400)
+        .await
+        .unwrap();
+    let (context, _) = das_api.reader(Some(350)).await.unwrap();
+    assert_eq!(context.slot, 400);
+
+    let err = das_api.reader(Some(500)).await.err().unwrap();
+    assert!(matches!(
+        err,
+        das_api::error::DasApiError::MinContextSlotNotReached(400)
+    ));
+}
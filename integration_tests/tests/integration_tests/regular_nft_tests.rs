@@ -1,11 +1,51 @@
 use function_name::named;
+use std::str::FromStr;
 
 use das_api::api::{self, ApiContract};
+use das_api::builder::RpcApiBuilder;
+
+use digital_asset_types::dao::{
+    asset, asset_data, asset_signatures, notify_asset_updated, token_accounts, tokens, Cursor,
+};
+use digital_asset_types::dapi::get_context;
+use digital_asset_types::rpc::{
+    filter::{AssetSortBy, AssetSortDirection, AssetSorting},
+    Asset,
+};
 
 use itertools::Itertools;
 
+use jsonrpsee::core::{
+    params::{ArrayParams, ObjectParams},
+    server::rpc_module::Subscription,
+};
+
+use mpl_token_metadata::accounts::Metadata;
+
+use nft_ingester::{
+    progress::{clear_failed_messages, record_failed_messages, record_stream_progress},
+    tasks::save_asset_attributes,
+};
+
+use plerkle_serialization::root_as_transaction_info;
+
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, PaginatorTrait,
+    QueryFilter, Set, Statement, TransactionTrait,
+};
+
 use serial_test::serial;
 
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::Transaction,
+};
+
+use std::{collections::HashSet, time::Duration};
+
 use super::common::*;
 
 #[tokio::test]
@@ -144,3 +184,815 @@ async fn test_reg_search_assets() {
     let response = setup.das_api.search_assets(request).await.unwrap();
     insta::assert_json_snapshot!(name, response);
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_get_token_accounts() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    for (request, individual_test_name) in [
+        (
+            r#"
+        {
+            "ownerAddress": "6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX",
+            "limit": 1
+        }
+        "#,
+            "by-owner",
+        ),
+        (
+            r#"
+        {
+            "mintAddress": "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL"
+        }
+        "#,
+            "by-mint",
+        ),
+    ] {
+        let request: api::GetTokenAccounts = serde_json::from_str(request).unwrap();
+        let response = setup.das_api.get_token_accounts(request).await.unwrap();
+        insta::assert_json_snapshot!(format!("{}-{}", name, individual_test_name), response);
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_get_assets_by_owner_show_fungible() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let request = r#"
+    {
+        "ownerAddress": "6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX",
+        "page": 1,
+        "limit": 2,
+        "options": {
+            "showFungible": true
+        }
+    }
+    "#;
+
+    let request: api::GetAssetsByOwner = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.get_assets_by_owner(request).await.unwrap();
+    insta::assert_json_snapshot!(name, response);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_by_name_and_symbol() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK",
+        "2NqdYX6kJmMUoChnDXU2UrP9BsoPZivRw3uJG8iDhRRd",
+        "5rEeYv8R25b8j6YTHJvYuCKEzq44UCw1Wx1Wx2VPPLz1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    for (request, individual_test_name) in [
+        (
+            r#"
+        {
+            "name": "mad lads #996",
+            "textMatchMode": "prefix",
+            "caseInsensitive": true
+        }
+        "#,
+            "name-prefix-case-insensitive",
+        ),
+        (
+            r#"
+        {
+            "name": "Lads #9959"
+        }
+        "#,
+            "name-contains",
+        ),
+        (
+            r#"
+        {
+            "name": "Mad Lads #99_9"
+        }
+        "#,
+            "name-wildcard-is-literal",
+        ),
+        (
+            r#"
+        {
+            "symbol": "MAD",
+            "textMatchMode": "exact"
+        }
+        "#,
+            "symbol-exact",
+        ),
+    ] {
+        let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+        let response = setup.das_api.search_assets(request).await.unwrap();
+        insta::assert_json_snapshot!(format!("{}-{}", name, individual_test_name), response);
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_by_attributes() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK",
+        "2NqdYX6kJmMUoChnDXU2UrP9BsoPZivRw3uJG8iDhRRd",
+        "5rEeYv8R25b8j6YTHJvYuCKEzq44UCw1Wx1Wx2VPPLz1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    // Stand in for the metadata download task, which is not run in tests.
+    for (mint, metadata) in [
+        (
+            "HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK",
+            serde_json::json!({"attributes": [
+                {"trait_type": "Background", "value": "Blue"},
+                {"trait_type": "Expression", "value": "Smile"},
+            ]}),
+        ),
+        (
+            "2NqdYX6kJmMUoChnDXU2UrP9BsoPZivRw3uJG8iDhRRd",
+            serde_json::json!({"attributes": [
+                {"trait_type": "Background", "value": "Blue"},
+                {"trait_type": "Expression", "value": "Frown"},
+            ]}),
+        ),
+        (
+            "5rEeYv8R25b8j6YTHJvYuCKEzq44UCw1Wx1Wx2VPPLz1",
+            serde_json::json!({"attributes": [
+                {"trait_type": "Background", "value": "Red"},
+                {"trait_type": "Level", "value": 3},
+            ]}),
+        ),
+    ] {
+        let asset_data_id = Pubkey::from_str(mint).unwrap().to_bytes();
+        save_asset_attributes(setup.db.as_ref(), &asset_data_id, &metadata)
+            .await
+            .unwrap();
+    }
+
+    for (request, individual_test_name) in [
+        (
+            r#"
+        {
+            "attributes": {
+                "traits": [
+                    {"traitType": "Background", "value": "Blue"},
+                    {"traitType": "Expression", "value": "Smile"}
+                ]
+            }
+        }
+        "#,
+            "all",
+        ),
+        (
+            r#"
+        {
+            "attributes": {
+                "conditionType": "any",
+                "traits": [
+                    {"traitType": "Expression", "value": "Frown"},
+                    {"trait_type": "Level", "value": "3"}
+                ]
+            }
+        }
+        "#,
+            "any",
+        ),
+    ] {
+        let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+        let response = setup.das_api.search_assets(request).await.unwrap();
+        insta::assert_json_snapshot!(format!("{}-{}", name, individual_test_name), response);
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_cursor_sorting() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK",
+        "2NqdYX6kJmMUoChnDXU2UrP9BsoPZivRw3uJG8iDhRRd",
+        "5rEeYv8R25b8j6YTHJvYuCKEzq44UCw1Wx1Wx2VPPLz1",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    // Ties and NULLs in the sort column must not break paging.
+    for sql in [
+        "UPDATE asset SET slot_updated = 1, created_at = '2024-01-01T00:00:00Z'",
+        "UPDATE asset SET slot_updated = NULL, created_at = NULL WHERE id = (SELECT id FROM asset ORDER BY id LIMIT 1)",
+        "UPDATE asset SET royalty_amount = 500, supply = 1",
+        "UPDATE asset_data SET raw_name = NULL WHERE id = (SELECT id FROM asset ORDER BY id DESC LIMIT 1)",
+        // The most recent action of an asset is the latest slot among its accounts and leaf.
+        "UPDATE asset SET slot_updated_token_account = 999999999999 WHERE id = (SELECT id FROM asset ORDER BY id LIMIT 1 OFFSET 1)",
+    ] {
+        setup
+            .db
+            .execute(Statement::from_string(DbBackend::Postgres, sql.to_string()))
+            .await
+            .unwrap();
+    }
+
+    for sort_by in [
+        r#"{"sortBy": "created", "sortDirection": "asc"}"#,
+        r#"{"sortBy": "created", "sortDirection": "desc"}"#,
+        r#"{"sortBy": "recent_action", "sortDirection": "asc"}"#,
+        r#"{"sortBy": "recent_action", "sortDirection": "desc"}"#,
+        r#"{"sortBy": "updated", "sortDirection": "desc"}"#,
+        r#"{"sortBy": "name", "sortDirection": "asc"}"#,
+        r#"{"sortBy": "name", "sortDirection": "desc"}"#,
+        r#"{"sortBy": "royalty_amount", "sortDirection": "asc"}"#,
+        r#"{"sortBy": "supply", "sortDirection": "desc"}"#,
+        r#"{"sortBy": "none"}"#,
+    ] {
+        let sort_by: AssetSorting = serde_json::from_str(sort_by).unwrap();
+        let request = api::SearchAssets {
+            sort_by: Some(sort_by),
+            page: Some(1),
+            ..Default::default()
+        };
+        let expected = setup.das_api.search_assets(request).await.unwrap();
+        let expected = expected.items.into_iter().map(|a| a.id).collect_vec();
+
+        let mut ids = vec![];
+        let mut cursor = None;
+        loop {
+            let request = api::SearchAssets {
+                sort_by: Some(sort_by),
+                limit: Some(1),
+                cursor,
+                ..Default::default()
+            };
+            let response = setup.das_api.search_assets(request).await.unwrap();
+            if response.items.is_empty() {
+                break;
+            }
+            ids.extend(response.items.into_iter().map(|a| a.id));
+            cursor = response.cursor;
+        }
+
+        if sort_by.sort_by == AssetSortBy::None {
+            // Without sorting only the set of assets is stable.
+            assert_eq!(ids.len(), 3);
+        } else {
+            assert_eq!(ids, expected, "{:?}", sort_by);
+        }
+        if sort_by.sort_by == AssetSortBy::RecentAction
+            && sort_by.sort_direction == Some(AssetSortDirection::Desc)
+        {
+            let mut by_id = ids.clone();
+            by_id.sort_by_key(|id| Pubkey::from_str(id).unwrap().to_bytes());
+            assert_eq!(ids[0], by_id[1]);
+        }
+        if sort_by.sort_by == AssetSortBy::Name {
+            let mut names = vec![];
+            for id in &ids {
+                let id = Pubkey::from_str(id).unwrap().to_bytes().to_vec();
+                let data = asset_data::Entity::find_by_id(id)
+                    .one(setup.db.as_ref())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                names.push(data.raw_name);
+            }
+            let mut sorted = names.clone();
+            sorted.sort();
+            // NULLs sort last ascending, first descending, like in Postgres.
+            let nulls = sorted.iter().filter(|n| n.is_none()).count();
+            sorted.rotate_left(nulls);
+            if sort_by.sort_direction == Some(AssetSortDirection::Desc) {
+                sorted.reverse();
+            }
+            assert_eq!(names, sorted);
+        }
+    }
+
+    // A cursor can only be used with the sorting it was issued for.
+    let request: api::SearchAssets = serde_json::from_str(
+        r#"{"sortBy": {"sortBy": "created"}, "limit": 1, "cursor": "2NqdYX6kJmMUoChnDXU2UrP9BsoPZivRw3uJG8iDhRRd"}"#,
+    )
+    .unwrap();
+    assert!(setup.das_api.search_assets(request).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_show_grand_total() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W",
+        "BioVudBTjJnuDW22q62XPhGP87sVwZKcQ46MPSNz4gqi",
+        "Fm9S3FL23z3ii3EBBv8ozqLninLvhWDYmcHcHaZy6nie",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let options = r#""options": {"showGrandTotal": true}, "limit": 1, "page": 1"#;
+
+    let request = format!(
+        r#"{{"groupKey": "collection", "groupValue": "8Rt3Ayqth4DAiPnW9MDFi63TiQJHmohfTWLMQFHi4KZH", {}}}"#,
+        options
+    );
+    let request: api::GetAssetsByGroup = serde_json::from_str(&request).unwrap();
+    let by_group = setup.das_api.get_assets_by_group(request).await.unwrap();
+
+    let request = format!(
+        r#"{{"creatorAddress": "HV4Nvm9zHfNA43JYYkjZu8vwqiuE8bfEhwcKFfyQ65o5", {}}}"#,
+        options
+    );
+    let request: api::GetAssetsByCreator = serde_json::from_str(&request).unwrap();
+    let by_creator = setup.das_api.get_assets_by_creator(request).await.unwrap();
+
+    let request = format!(
+        r#"{{"authorityAddress": "GfELr1GA9bLmgiMymUm7h8nDkZLG2Ls6txSsANopeVEW", {}}}"#,
+        options
+    );
+    let request: api::GetAssetsByAuthority = serde_json::from_str(&request).unwrap();
+    let by_authority = setup
+        .das_api
+        .get_assets_by_authority(request)
+        .await
+        .unwrap();
+
+    let request = format!(
+        r#"{{"ownerAddress": "9qUcfdADyrrTSetFjNjF9Ro7LKAqzJkzZV6WKLHfv5MU", {}}}"#,
+        options
+    );
+    let request: api::GetAssetsByOwner = serde_json::from_str(&request).unwrap();
+    let by_owner = setup.das_api.get_assets_by_owner(request).await.unwrap();
+
+    let request = format!(
+        r#"{{"grouping": ["collection", "8Rt3Ayqth4DAiPnW9MDFi63TiQJHmohfTWLMQFHi4KZH"], {}}}"#,
+        options
+    );
+    let request: api::SearchAssets = serde_json::from_str(&request).unwrap();
+    let search = setup.das_api.search_assets(request).await.unwrap();
+
+    let totals = serde_json::json!({
+        "by_group": [by_group.total, by_group.grand_total],
+        "by_creator": [by_creator.total, by_creator.grand_total],
+        "by_authority": [by_authority.total, by_authority.grand_total],
+        "by_owner": [by_owner.total, by_owner.grand_total],
+        "search": [search.total, search.grand_total],
+    });
+    insta::assert_json_snapshot!(name, totals);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_asset_subscriptions() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W",
+        "BioVudBTjJnuDW22q62XPhGP87sVwZKcQ46MPSNz4gqi",
+        "Fm9S3FL23z3ii3EBBv8ozqLninLvhWDYmcHcHaZy6nie",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let das_api = api::DasApi::from_config(das_api::config::Config {
+        database_url: std::env::var("DATABASE_TEST_URL").unwrap(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let module = RpcApiBuilder::build(Box::new(das_api)).unwrap();
+
+    let mut params = ObjectParams::new();
+    params
+        .insert("id", "7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W")
+        .unwrap();
+    let mut asset_subscription = module.subscribe("assetSubscribe", params).await.unwrap();
+
+    let mut params = ObjectParams::new();
+    params
+        .insert(
+            "ownerAddress",
+            "9qUcfdADyrrTSetFjNjF9Ro7LKAqzJkzZV6WKLHfv5MU",
+        )
+        .unwrap();
+    let mut owner_subscription = module.subscribe("ownerSubscribe", params).await.unwrap();
+
+    let mut params = ObjectParams::new();
+    params.insert("groupKey", "collection").unwrap();
+    params
+        .insert("groupValue", "8Rt3Ayqth4DAiPnW9MDFi63TiQJHmohfTWLMQFHi4KZH")
+        .unwrap();
+    let mut group_subscription = module.subscribe("groupSubscribe", params).await.unwrap();
+
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let asset = next_asset_update(&mut asset_subscription).await;
+    assert_eq!(asset.id, "7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W");
+
+    let asset = next_asset_update(&mut owner_subscription).await;
+    assert_eq!(
+        asset.ownership.owner,
+        "9qUcfdADyrrTSetFjNjF9Ro7LKAqzJkzZV6WKLHfv5MU"
+    );
+
+    let mut group_ids = HashSet::new();
+    while group_ids.len() < 3 {
+        group_ids.insert(next_asset_update(&mut group_subscription).await.id);
+    }
+    assert_eq!(
+        group_ids,
+        HashSet::from([
+            "7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W".to_string(),
+            "BioVudBTjJnuDW22q62XPhGP87sVwZKcQ46MPSNz4gqi".to_string(),
+            "Fm9S3FL23z3ii3EBBv8ozqLninLvhWDYmcHcHaZy6nie".to_string(),
+        ])
+    );
+
+    let mut params = ObjectParams::new();
+    params.insert("id", "not a pubkey").unwrap();
+    assert!(module.subscribe("assetSubscribe", params).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_asset_burn_subscription() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts(["7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W"]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let das_api = api::DasApi::from_config(das_api::config::Config {
+        database_url: std::env::var("DATABASE_TEST_URL").unwrap(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let module = RpcApiBuilder::build(Box::new(das_api)).unwrap();
+
+    let mut params = ObjectParams::new();
+    params
+        .insert("id", "7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W")
+        .unwrap();
+    let mut subscription = module.subscribe("assetSubscribe", params).await.unwrap();
+
+    // Burning a token metadata NFT takes its supply to zero.
+    let id = Pubkey::from_str("7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W").unwrap();
+    let txn = setup.db.begin().await.unwrap();
+    asset::Entity::update_many()
+        .col_expr(asset::Column::Supply, Expr::value(0))
+        .col_expr(asset::Column::Burnt, Expr::value(true))
+        .filter(asset::Column::Id.eq(id.to_bytes().to_vec()))
+        .exec(&txn)
+        .await
+        .unwrap();
+    notify_asset_updated(&txn, id.as_ref()).await.unwrap();
+    txn.commit().await.unwrap();
+
+    loop {
+        let asset = next_asset_update(&mut subscription).await;
+        assert_eq!(asset.id, "7jFuJ73mBPDdLMvCYxzrpFTD9FeDudRxdXGDALP5Cp2W");
+        if asset.burnt {
+            break;
+        }
+    }
+}
+
+async fn next_asset_update(subscription: &mut Subscription) -> Asset {
+    let (asset, _) = tokio::time::timeout(Duration::from_secs(10), subscription.next::<Asset>())
+        .await
+        .expect("timed out waiting for an asset update")
+        .unwrap()
+        .unwrap();
+    asset
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_get_asset_signatures() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let mint = Pubkey::from_str("CMVuYDS9nTeujfTPJb8ik7CRhAqZv4DfjfdamFLkJgxE").unwrap();
+    let seeds: Vec<SeedEvent> = seed_nfts([mint.to_string()]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let token_account = token_accounts::Entity::find()
+        .filter(token_accounts::Column::Mint.eq(mint.to_bytes().to_vec()))
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    let token_account_pubkey = Pubkey::try_from(token_account.pubkey).unwrap();
+    let owner = Pubkey::try_from(token_account.owner).unwrap();
+    let (metadata, _) = Metadata::find_pda(&mint);
+
+    let fungible_mint = Pubkey::new_unique();
+    tokens::Entity::insert(tokens::ActiveModel {
+        mint: Set(fungible_mint.to_bytes().to_vec()),
+        supply: Set(1_000_000),
+        decimals: Set(6),
+        token_program: Set(spl_token::id().to_bytes().to_vec()),
+        slot_updated: Set(1),
+        ..Default::default()
+    })
+    .exec(setup.db.as_ref())
+    .await
+    .unwrap();
+
+    // Only the mint's metadata account and token account tie these transactions to the asset.
+    let instructions = [
+        Instruction::new_with_bytes(
+            mpl_token_metadata::ID,
+            &[50],
+            vec![
+                AccountMeta::new(metadata, false),
+                AccountMeta::new_readonly(mint, false),
+            ],
+        ),
+        spl_token::instruction::transfer(
+            &spl_token::id(),
+            &token_account_pubkey,
+            &Pubkey::new_unique(),
+            &owner,
+            &[],
+            1,
+        )
+        .unwrap(),
+        spl_token::instruction::transfer(
+            &spl_token::id(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[],
+            1,
+        )
+        .unwrap(),
+        spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &fungible_mint,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[],
+            1,
+        )
+        .unwrap(),
+        // A Token-2022 transfer that only names the mint.
+        Instruction::new_with_bytes(
+            Pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").unwrap(),
+            &[12],
+            vec![
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ],
+        ),
+    ];
+    for (i, instruction) in instructions.into_iter().enumerate() {
+        let payer = Pubkey::new_unique();
+        let mut transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+        transaction.signatures = vec![
+            Signature::from([i as u8 + 1; 64]);
+            transaction.message.header.num_required_signatures as usize
+        ];
+        let txn_bytes = serialize_local_transaction(100 + i as u64, transaction);
+        let txn = root_as_transaction_info(&txn_bytes).unwrap();
+        setup.transformer.handle_transaction(&txn).await.unwrap();
+    }
+
+    let all = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(mint.to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(all.items.len(), 3);
+    let fungible_signatures = asset_signatures::Entity::find()
+        .filter(asset_signatures::Column::AssetId.eq(fungible_mint.to_bytes().to_vec()))
+        .count(setup.db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(fungible_signatures, 0);
+
+    let first = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(mint.to_string()),
+            limit: Some(1),
+            sort_direction: Some(AssetSortDirection::Asc),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let second = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(mint.to_string()),
+            limit: Some(1),
+            cursor: first.cursor.clone(),
+            sort_direction: Some(AssetSortDirection::Asc),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        [second.items.clone(), first.items.clone()].concat(),
+        all.items[1..]
+    );
+
+    let bad_cursor = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(mint.to_string()),
+            cursor: Cursor {
+                id: Some(vec![1, 2, 3]),
+                sort_value: None,
+            }
+            .encode(),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        bad_cursor,
+        Err(das_api::error::DasApiError::PaginationError)
+    ));
+
+    let keyset = setup
+        .das_api
+        .get_asset_signatures(api::GetAssetSignatures {
+            id: Some(mint.to_string()),
+            after: Some(Pubkey::new_unique().to_string()),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        keyset,
+        Err(das_api::error::DasApiError::PaginationKeysetUnsupportedError)
+    ));
+
+    insta::assert_json_snapshot!(name, all.items);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_context_slot() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts(["HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK"]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    // The context is the slot the slowest consumer of any stream is through, short of the
+    // messages waiting to be retried.
+    for (stream, consumer, slot) in [
+        ("ACC", "a", 300),
+        ("ACC", "b", 280),
+        ("TXN", "c", 200),
+        ("TXN", "d", 260),
+        ("TXN", "c", 270),
+        ("TXN", "gone", 10),
+    ] {
+        record_stream_progress(setup.db.as_ref(), stream, consumer, slot)
+            .await
+            .unwrap();
+    }
+    record_failed_messages(setup.db.as_ref(), "TXN", vec![("1-0".to_string(), 251)])
+        .await
+        .unwrap();
+    // Consumers that stopped making progress long ago have gone away.
+    setup
+        .db
+        .execute(Statement::from_string(
+            DbBackend::Postgres,
+            "UPDATE ingester_progress SET updated_at = updated_at - INTERVAL '1 hour' WHERE consumer = 'gone'"
+                .to_string(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(get_context(setup.db.as_ref()).await.unwrap().slot, 250);
+    clear_failed_messages(setup.db.as_ref(), "TXN", vec!["1-0".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(get_context(setup.db.as_ref()).await.unwrap().slot, 260);
+    // A consumer moves back while it handles messages for earlier slots.
+    record_stream_progress(setup.db.as_ref(), "TXN", "d", 250)
+        .await
+        .unwrap();
+
+    let das_api = api::DasApi::from_config(das_api::config::Config {
+        database_url: std::env::var("DATABASE_TEST_URL").unwrap(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let module = RpcApiBuilder::build(Box::new(das_api)).unwrap();
+
+    let get_asset = |min_context_slot: Option<u64>| {
+        let mut params = ObjectParams::new();
+        params
+            .insert("id", "HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK")
+            .unwrap();
+        if let Some(slot) = min_context_slot {
+            params.insert("minContextSlot", slot).unwrap();
+        }
+        module.call::<_, serde_json::Value>("getAsset", params)
+    };
+
+    // Every result comes wrapped in its context.
+    let response = get_asset(None).await.unwrap();
+    assert_eq!(response["context"], serde_json::json!({ "slot": 250 }));
+    assert_eq!(
+        response["value"]["id"],
+        "HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK"
+    );
+
+    // Without a `minContextSlot` the context is the slot seen by the last health check, so
+    // progress made since then only shows once the slot is asked for.
+    record_stream_progress(setup.db.as_ref(), "TXN", "d", 255)
+        .await
+        .unwrap();
+    let response = get_asset(None).await.unwrap();
+    assert_eq!(response["context"]["slot"], 250);
+    let response = get_asset(Some(251)).await.unwrap();
+    assert_eq!(response["context"]["slot"], 255);
+
+    let err = get_asset(Some(256)).await.unwrap_err();
+    match err {
+        jsonrpsee::core::Error::Call(jsonrpsee::types::error::CallError::Custom(err)) => {
+            assert_eq!(err.code(), -32016);
+            assert_eq!(
+                err.data().map(|data| data.get()),
+                Some(r#"{"contextSlot":255}"#)
+            );
+        }
+        err => panic!("unexpected error {:?}", err),
+    }
+
+    // Positional params can't carry a `minContextSlot`.
+    let mut params = ArrayParams::new();
+    params
+        .insert("HTKAVZZrDdyecCxzm3WEkCsG1GUmiqKm73PvngfuYRNK")
+        .unwrap();
+    params
+        .insert(serde_json::json!({ "minContextSlot": 255 }))
+        .unwrap();
+    let err = module
+        .call::<_, serde_json::Value>("getAsset", params)
+        .await
+        .unwrap_err();
+    match err {
+        jsonrpsee::core::Error::Call(jsonrpsee::types::error::CallError::Custom(err)) => {
+            assert_eq!(err.code(), -32602);
+        }
+        err => panic!("unexpected error {:?}", err),
+    }
+}
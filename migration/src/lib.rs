@@ -38,6 +38,21 @@ mod m20240116_130744_add_update_metadata_ix;
 mod m20240117_120101_alter_creator_indices;
 mod m20240124_173104_add_tree_seq_index_to_cl_audits_v2;
 mod m20240124_181900_add_slot_updated_column_per_update_type;
+mod m20240320_120101_add_token_accounts_owner_index;
+mod m20240321_120101_add_asset_data_name_symbol_trgm_indexes;
+mod m20240322_120101_add_asset_attributes;
+mod m20240323_120101_add_cl_items_history;
+mod m20240325_120101_add_merkle_tree;
+mod m20240326_120101_add_cl_audits_v2_slot;
+mod m20240327_120101_add_asset_signatures;
+mod m20240328_120101_add_asset_events;
+mod m20240329_120101_add_merkle_tree_config;
+mod m20240330_120101_add_asset_tree_nonce_index;
+mod m20240331_120101_add_asset_delegate_id_index;
+mod m20240401_120101_add_ingester_progress;
+mod m20240402_120101_add_ingester_progress_backlog;
+mod m20240403_120101_track_ingester_consumers;
+mod m20240404_120101_add_asset_events_type_index;
 
 pub mod model;
 
@@ -85,6 +100,21 @@ impl MigratorTrait for Migrator {
             Box::new(m20240117_120101_alter_creator_indices::Migration),
             Box::new(m20240124_173104_add_tree_seq_index_to_cl_audits_v2::Migration),
             Box::new(m20240124_181900_add_slot_updated_column_per_update_type::Migration),
+            Box::new(m20240320_120101_add_token_accounts_owner_index::Migration),
+            Box::new(m20240321_120101_add_asset_data_name_symbol_trgm_indexes::Migration),
+            Box::new(m20240322_120101_add_asset_attributes::Migration),
+            Box::new(m20240323_120101_add_cl_items_history::Migration),
+            Box::new(m20240325_120101_add_merkle_tree::Migration),
+            Box::new(m20240326_120101_add_cl_audits_v2_slot::Migration),
+            Box::new(m20240327_120101_add_asset_signatures::Migration),
+            Box::new(m20240328_120101_add_asset_events::Migration),
+            Box::new(m20240329_120101_add_merkle_tree_config::Migration),
+            Box::new(m20240330_120101_add_asset_tree_nonce_index::Migration),
+            Box::new(m20240331_120101_add_asset_delegate_id_index::Migration),
+            Box::new(m20240401_120101_add_ingester_progress::Migration),
+            Box::new(m20240402_120101_add_ingester_progress_backlog::Migration),
+            Box::new(m20240403_120101_track_ingester_consumers::Migration),
+            Box::new(m20240404_120101_add_asset_events_type_index::Migration),
         ]
     }
 }
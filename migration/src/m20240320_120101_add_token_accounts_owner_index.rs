@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::TokenAccounts;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves `getTokenAccounts` queries filtered by owner, optionally narrowed down by mint.
+        manager
+            .create_index(
+                Index::create()
+                    .name("ta_owner_mint")
+                    .table(TokenAccounts::Table)
+                    .col(TokenAccounts::Owner)
+                    .col(TokenAccounts::Mint)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("ta_owner_mint")
+                    .table(TokenAccounts::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
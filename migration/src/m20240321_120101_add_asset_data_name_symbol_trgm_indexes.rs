@@ -0,0 +1,54 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        // Trigram indexes serve `searchAssets` name and symbol lookups for exact, prefix and
+        // substring patterns, with both `LIKE` and `ILIKE`.
+        //
+        // They are built over the sanitized copies kept in `chain_data`: `raw_name` and
+        // `raw_symbol` are padded byte strings that are not guaranteed to be valid UTF-8.
+        //
+        // The extension lives in its own schema so that its types are not mistaken for ours,
+        // e.g. when the public schema is reset with `migration fresh`.
+        for stmt in [
+            "CREATE SCHEMA IF NOT EXISTS extensions;",
+            "CREATE EXTENSION IF NOT EXISTS pg_trgm WITH SCHEMA extensions;",
+            "CREATE INDEX IF NOT EXISTS asset_data_name_trgm ON asset_data USING GIN ((chain_data->>'name') extensions.gin_trgm_ops);",
+            "CREATE INDEX IF NOT EXISTS asset_data_symbol_trgm ON asset_data USING GIN ((chain_data->>'symbol') extensions.gin_trgm_ops);",
+        ] {
+            conn.execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                stmt.to_string(),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        for stmt in [
+            "DROP INDEX IF EXISTS asset_data_name_trgm;",
+            "DROP INDEX IF EXISTS asset_data_symbol_trgm;",
+        ] {
+            conn.execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                stmt.to_string(),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::AssetAttributes;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AssetAttributes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AssetAttributes::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetAttributes::AssetDataId)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AssetAttributes::TraitType).text().not_null())
+                    .col(ColumnDef::new(AssetAttributes::Value).text().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Serves `searchAssets` attribute filters.
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_attributes_trait_type_value")
+                    .table(AssetAttributes::Table)
+                    .col(AssetAttributes::TraitType)
+                    .col(AssetAttributes::Value)
+                    .col(AssetAttributes::AssetDataId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Attributes are replaced per asset data whenever its metadata is downloaded.
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_attributes_asset_data_id")
+                    .table(AssetAttributes::Table)
+                    .col(AssetAttributes::AssetDataId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetAttributes::Table).to_owned())
+            .await
+    }
+}
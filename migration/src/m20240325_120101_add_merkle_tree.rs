@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::MerkleTree;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MerkleTree::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MerkleTree::Id)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MerkleTree::MaxDepth).integer().not_null())
+                    .col(
+                        ColumnDef::new(MerkleTree::MaxBufferSize)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MerkleTree::CanopyDepth).integer().not_null())
+                    .col(
+                        ColumnDef::new(MerkleTree::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MerkleTree::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::ClAuditsV2;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClAuditsV2::Table)
+                    .add_column(ColumnDef::new(ClAuditsV2::Slot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClAuditsV2::Table)
+                    .drop_column(ClAuditsV2::Slot)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::AssetSignatures;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AssetSignatures::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AssetSignatures::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(AssetSignatures::AssetId).binary().not_null())
+                    .col(ColumnDef::new(AssetSignatures::Tx).binary().not_null())
+                    .col(
+                        ColumnDef::new(AssetSignatures::Slot)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetSignatures::CreatedAt)
+                            .date_time()
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .unique()
+                    .name("asset_signatures_asset_id_tx")
+                    .table(AssetSignatures::Table)
+                    .col(AssetSignatures::AssetId)
+                    .col(AssetSignatures::Tx)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Serves the history of an asset in slot order.
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_signatures_asset_id_slot_id")
+                    .table(AssetSignatures::Table)
+                    .col(AssetSignatures::AssetId)
+                    .col(AssetSignatures::Slot)
+                    .col(AssetSignatures::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetSignatures::Table).to_owned())
+            .await
+    }
+}
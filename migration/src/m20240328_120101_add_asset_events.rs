@@ -0,0 +1,83 @@
+use enum_iterator::all;
+use sea_orm_migration::{prelude::*, sea_query::extension::postgres::Type};
+
+use crate::model::r#enum::AssetEventType;
+use crate::model::table::AssetEvents;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("asset_event_type"))
+                    .values(all::<AssetEventType>().collect::<Vec<_>>())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AssetEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AssetEvents::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(AssetEvents::AssetId).binary().not_null())
+                    .col(
+                        ColumnDef::new(AssetEvents::EventType)
+                            .enumeration(
+                                Alias::new("asset_event_type"),
+                                all::<AssetEventType>().collect::<Vec<_>>(),
+                            )
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AssetEvents::Seq).big_integer().not_null())
+                    .col(ColumnDef::new(AssetEvents::Slot).big_integer().not_null())
+                    .col(ColumnDef::new(AssetEvents::Tx).binary().not_null())
+                    .col(ColumnDef::new(AssetEvents::FromAddress).binary())
+                    .col(ColumnDef::new(AssetEvents::ToAddress).binary())
+                    .col(
+                        ColumnDef::new(AssetEvents::CreatedAt)
+                            .date_time()
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every change to a leaf has its own sequence number, so this also serves the history of
+        // an asset in order.
+        manager
+            .create_index(
+                Index::create()
+                    .unique()
+                    .name("asset_events_asset_id_seq")
+                    .table(AssetEvents::Table)
+                    .col(AssetEvents::AssetId)
+                    .col(AssetEvents::Seq)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetEvents::Table).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(Alias::new("asset_event_type")).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::MerkleTree;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The tree config columns are filled in from `CreateTree` and the bubblegum tree config
+        // account, which may arrive before or after the tree account itself.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MerkleTree::Table)
+                    .add_column(ColumnDef::new(MerkleTree::Seq).big_integer())
+                    .add_column(ColumnDef::new(MerkleTree::TreeConfig).binary())
+                    .add_column(ColumnDef::new(MerkleTree::TreeCreator).binary())
+                    .add_column(ColumnDef::new(MerkleTree::TreeDelegate).binary())
+                    .add_column(ColumnDef::new(MerkleTree::IsPublic).boolean())
+                    .add_column(ColumnDef::new(MerkleTree::NumMinted).big_integer())
+                    .add_column(ColumnDef::new(MerkleTree::TotalMintCapacity).big_integer())
+                    .add_column(ColumnDef::new(MerkleTree::ConfigSlotUpdated).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tree config account updates only carry the config address.
+        manager
+            .create_index(
+                Index::create()
+                    .name("merkle_tree_tree_config")
+                    .table(MerkleTree::Table)
+                    .col(MerkleTree::TreeConfig)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("merkle_tree_tree_config")
+                    .table(MerkleTree::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MerkleTree::Table)
+                    .drop_column(MerkleTree::Seq)
+                    .drop_column(MerkleTree::TreeConfig)
+                    .drop_column(MerkleTree::TreeCreator)
+                    .drop_column(MerkleTree::TreeDelegate)
+                    .drop_column(MerkleTree::IsPublic)
+                    .drop_column(MerkleTree::NumMinted)
+                    .drop_column(MerkleTree::TotalMintCapacity)
+                    .drop_column(MerkleTree::ConfigSlotUpdated)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::Asset;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves `getAssetsByTree`, which walks the leaves of a tree by index.
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_tree_nonce")
+                    .table(Asset::Table)
+                    .col(Asset::TreeId)
+                    .col(Asset::Nonce)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("asset_tree_nonce")
+                    .table(Asset::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::Asset;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves `getAssetsByDelegate`, whose pages are ordered by id by default. It also covers
+        // the lookups of the plain delegate index it replaces.
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_delegate_id")
+                    .table(Asset::Table)
+                    .col(Asset::Delegate)
+                    .col(Asset::Id)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "DROP INDEX IF EXISTS asset_delegate;".to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_delegate")
+                    .table(Asset::Table)
+                    .col(Asset::Delegate)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("asset_delegate_id")
+                    .table(Asset::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
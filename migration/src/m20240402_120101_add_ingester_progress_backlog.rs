@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::IngesterProgress;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(IngesterProgress::Table)
+                    .add_column(ColumnDef::new(IngesterProgress::Backlog).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(IngesterProgress::Table)
+                    .drop_column(IngesterProgress::Backlog)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+
+use crate::model::table::IngesterFailedMessages;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Progress is tracked per stream consumer from now on, the per stream rows are dropped.
+        for sql in [
+            "DELETE FROM ingester_progress",
+            "
+            ALTER TABLE ingester_progress
+            ADD COLUMN consumer VARCHAR NOT NULL,
+            DROP CONSTRAINT ingester_progress_pkey,
+            ADD PRIMARY KEY (stream, consumer)
+            ",
+        ] {
+            manager
+                .get_connection()
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    sql.to_string(),
+                ))
+                .await?;
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(IngesterFailedMessages::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(IngesterFailedMessages::Stream)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(IngesterFailedMessages::MessageId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(IngesterFailedMessages::Slot)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(IngesterFailedMessages::UpdatedAt)
+                            .date_time()
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp))
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(IngesterFailedMessages::Stream)
+                            .col(IngesterFailedMessages::MessageId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(IngesterFailedMessages::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for sql in [
+            "DELETE FROM ingester_progress",
+            "
+            ALTER TABLE ingester_progress
+            DROP COLUMN consumer,
+            ADD PRIMARY KEY (stream)
+            ",
+        ] {
+            manager
+                .get_connection()
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    sql.to_string(),
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
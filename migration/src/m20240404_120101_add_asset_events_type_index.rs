@@ -0,0 +1,38 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::AssetEvents;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves `getCollectionStats`, which looks up the first mint slot of every member. With
+        // the slot in the index, each lookup reads a single entry.
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_events_asset_id_event_type_slot")
+                    .table(AssetEvents::Table)
+                    .col(AssetEvents::AssetId)
+                    .col(AssetEvents::EventType)
+                    .col(AssetEvents::Slot)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("asset_events_asset_id_event_type_slot")
+                    .table(AssetEvents::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
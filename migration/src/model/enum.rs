@@ -29,3 +29,19 @@ pub enum BubblegumInstruction {
     // We need to use an alias instead.
     // UpdateMetadata,
 }
+
+#[derive(Iden, Debug, PartialEq, Sequence)]
+pub enum AssetEventType {
+    Mint,
+    Transfer,
+    Delegate,
+    Undelegate,
+    Burn,
+    Redeem,
+    CancelRedeem,
+    VerifyCollection,
+    UnverifyCollection,
+    VerifyCreator,
+    UnverifyCreator,
+    UpdateMetadata,
+}
@@ -169,4 +169,85 @@ pub enum ClAuditsV2 {
     CreatedAt,
     Tx,
     Instruction,
+    Slot,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum AssetAttributes {
+    Table,
+    Id,
+    AssetDataId,
+    TraitType,
+    Value,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum ClItemsHistory {
+    Table,
+    Id,
+    Tree,
+    NodeIdx,
+    Seq,
+    Level,
+    Hash,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum MerkleTree {
+    Table,
+    Id,
+    MaxDepth,
+    MaxBufferSize,
+    CanopyDepth,
+    SlotUpdated,
+    Seq,
+    TreeConfig,
+    TreeCreator,
+    TreeDelegate,
+    IsPublic,
+    NumMinted,
+    TotalMintCapacity,
+    ConfigSlotUpdated,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum AssetSignatures {
+    Table,
+    Id,
+    AssetId,
+    Tx,
+    Slot,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum AssetEvents {
+    Table,
+    Id,
+    AssetId,
+    EventType,
+    Seq,
+    Slot,
+    Tx,
+    FromAddress,
+    ToAddress,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum IngesterProgress {
+    Table,
+    Stream,
+    Slot,
+    UpdatedAt,
+    Backlog,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum IngesterFailedMessages {
+    Table,
+    Stream,
+    MessageId,
+    Slot,
+    UpdatedAt,
 }
@@ -1,13 +1,18 @@
 use std::sync::Arc;
 
 use crate::{
-    metric, metrics::capture_result, program_transformers::ProgramTransformer, tasks::TaskData,
+    metric,
+    metrics::capture_result,
+    program_transformers::ProgramTransformer,
+    progress::{ConsumerProgress, HandledMessage},
+    tasks::TaskData,
 };
 use cadence_macros::{is_global_default_set, statsd_count, statsd_time};
 use chrono::Utc;
 use log::{debug, error};
 use plerkle_messenger::{ConsumptionType, Messenger, MessengerConfig, RecvData};
 use plerkle_serialization::root_as_account_info;
+use sea_orm::SqlxPostgresConnector;
 use sqlx::{Pool, Postgres};
 use tokio::{
     sync::mpsc::UnboundedSender,
@@ -21,18 +26,36 @@ pub fn account_worker<T: Messenger>(
     bg_task_sender: UnboundedSender<TaskData>,
     ack_channel: UnboundedSender<(&'static str, String)>,
     consumption_type: ConsumptionType,
+    token_account_updates: bool,
     stream_key: &'static str,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
+        let mut progress = ConsumerProgress::new(stream_key, &config, &consumption_type);
         let source = T::new(config).await;
         if let Ok(mut msg) = source {
-            let manager = Arc::new(ProgramTransformer::new(pool, bg_task_sender, false));
+            let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone());
+            let manager = Arc::new(ProgramTransformer::new(
+                pool,
+                bg_task_sender,
+                false,
+                false,
+                false,
+                token_account_updates,
+            ));
             loop {
                 let e = msg.recv(stream_key, consumption_type.clone()).await;
                 let mut tasks = JoinSet::new();
                 match e {
                     Ok(data) => {
                         let len = data.len();
+                        progress
+                            .start_batch(
+                                &conn,
+                                data.iter()
+                                    .filter_map(|item| root_as_account_info(&item.data).ok())
+                                    .map(|a| a.slot()),
+                            )
+                            .await;
                         for item in data {
                             tasks.spawn(handle_account(Arc::clone(&manager), item, stream_key));
                         }
@@ -47,29 +70,35 @@ pub fn account_worker<T: Messenger>(
                         }
                     }
                 }
+                let mut handled = Vec::new();
                 while let Some(res) = tasks.join_next().await {
-                    if let Ok(Some(id)) = res {
-                        let send = ack_channel.send((stream_key, id));
-                        if let Err(err) = send {
-                            metric! {
-                                    error!("Account stream ack error: {}", err);
-                                    statsd_count!("ingester.stream.ack_error", 1, "stream" => stream_key);
+                    if let Ok(Some(message)) = res {
+                        if message.acked {
+                            let send = ack_channel.send((stream_key, message.id.clone()));
+                            if let Err(err) = send {
+                                metric! {
+                                        error!("Account stream ack error: {}", err);
+                                        statsd_count!("ingester.stream.ack_error", 1, "stream" => stream_key);
+                                }
                             }
                         }
+                        handled.push(message);
                     }
                 }
+                progress.finish_batch(&conn, &handled).await;
             }
         }
     })
 }
 
+/// Returns the message along with whether to acknowledge it, unless it could not be read.
 async fn handle_account(
     manager: Arc<ProgramTransformer>,
     item: RecvData,
     stream_key: &'static str,
-) -> Option<String> {
+) -> Option<HandledMessage> {
     let id = item.id;
-    let mut ret_id = None;
+    let mut handled = None;
     let data = item.data;
     if item.tries > 0 {
         metric! {
@@ -96,7 +125,7 @@ async fn handle_account(
         }
         let begin_processing = Instant::now();
         let res = manager.handle_account_update(account_update).await;
-        let should_ack = capture_result(
+        let acked = capture_result(
             id.clone(),
             stream_key,
             ("owner", &str_program_id),
@@ -106,9 +135,12 @@ async fn handle_account(
             None,
             account,
         );
-        if should_ack {
-            ret_id = Some(id);
-        }
+        handled = Some(HandledMessage {
+            id,
+            slot: account_update.slot(),
+            tries: item.tries,
+            acked,
+        });
     }
-    ret_id
+    handled
 }
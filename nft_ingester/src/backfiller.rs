@@ -54,6 +54,7 @@ use crate::{
     config::{IngesterConfig, DATABASE_LISTENER_CHANNEL_KEY, RPC_COMMITMENT_KEY, RPC_URL_KEY},
     error::IngesterError,
     metric,
+    program_transformers::save_merkle_tree_header,
 };
 // Number of tries to backfill a single tree before marking as "failed".
 const NUM_TRIES: i32 = 5;
@@ -762,6 +763,20 @@ impl<'a, T: Messenger> Backfiller<'a, T> {
             let seq = u64::from_le_bytes(seq_bytes);
             list.insert(pubkey, SlotSeq(header.get_creation_slot(), seq));
 
+            // The header never changes after the tree is created, so its creation slot is a
+            // safe lower bound for the slot this data was read at. Failing to save it only
+            // leaves the tree out of `getTreeInfo`, so the tree is still backfilled.
+            if let Err(err) = save_merkle_tree_header(
+                &self.db,
+                pubkey.as_ref(),
+                &account.data,
+                header.get_creation_slot(),
+            )
+            .await
+            {
+                error!("Error saving the header of tree {}: {}", pubkey, err);
+            }
+
             if header.assert_valid_authority(&auth).is_err() {
                 continue;
             }
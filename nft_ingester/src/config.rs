@@ -31,6 +31,11 @@ pub struct IngesterConfig {
     pub code_version: Option<&'static str>,
     pub background_task_runner_config: Option<BackgroundTaskRunnerConfig>,
     pub cl_audits: Option<bool>, // save transaction logs for compressed nfts
+    pub cl_items_history: Option<bool>, // save past versions of tree nodes for proofs at a seq or root
+    pub cl_items_history_retention: Option<u64>, // sequence numbers of node history kept per tree
+    pub cl_items_history_backfill: Option<bool>, // start the node history from the indexed nodes
+    pub asset_signatures: Option<bool>, // save transaction signatures for uncompressed nfts
+    pub token_account_updates: Option<bool>, // announce owner changes from token accounts to subscriptions
 }
 
 #[derive(Deserialize, PartialEq, Debug, Clone)]
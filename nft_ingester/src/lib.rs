@@ -6,6 +6,8 @@ pub mod database;
 pub mod error;
 pub mod metrics;
 pub mod program_transformers;
+pub mod progress;
 pub mod stream;
 pub mod tasks;
 pub mod transaction_notifications;
+pub mod tree_history;
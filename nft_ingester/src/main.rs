@@ -6,9 +6,11 @@ mod database;
 pub mod error;
 pub mod metrics;
 mod program_transformers;
+mod progress;
 mod stream;
 pub mod tasks;
 mod transaction_notifications;
+mod tree_history;
 
 use crate::{
     account_updates::account_worker,
@@ -21,12 +23,14 @@ use crate::{
     stream::StreamSizeTimer,
     tasks::{BgTask, DownloadMetadataTask, TaskManager},
     transaction_notifications::transaction_worker,
+    tree_history::{setup_tree_history_backfill, setup_tree_history_pruning},
 };
 use cadence_macros::{is_global_default_set, statsd_count};
 use chrono::Duration;
 use clap::{arg, command, value_parser};
 use log::{error, info};
 use plerkle_messenger::{redis_messenger::RedisMessenger, ConsumptionType};
+use sea_orm::SqlxPostgresConnector;
 use std::{path::PathBuf, time};
 use tokio::{signal, task::JoinSet};
 
@@ -113,9 +117,13 @@ pub async fn main() -> Result<(), IngesterError> {
                 stream_name.as_str(),
             )?;
 
-            if let Some(t) = timer_worker.start::<RedisMessenger>().await {
-                tasks.spawn(t);
-            }
+            tasks.spawn(
+                timer_worker
+                    .start::<RedisMessenger>(SqlxPostgresConnector::from_sqlx_postgres_pool(
+                        database_pool.clone(),
+                    ))
+                    .await,
+            );
 
             for i in 0..worker.worker_count {
                 if worker.worker_type == WorkerType::Account {
@@ -129,6 +137,7 @@ pub async fn main() -> Result<(), IngesterError> {
                         } else {
                             ConsumptionType::New
                         },
+                        config.token_account_updates.unwrap_or(false),
                         stream_name,
                     );
                 } else if worker.worker_type == WorkerType::Transaction {
@@ -143,6 +152,8 @@ pub async fn main() -> Result<(), IngesterError> {
                             ConsumptionType::New
                         },
                         config.cl_audits.unwrap_or(false),
+                        config.cl_items_history.unwrap_or(false),
+                        config.asset_signatures.unwrap_or(false),
                         stream_name,
                     );
                 }
@@ -150,11 +161,20 @@ pub async fn main() -> Result<(), IngesterError> {
         }
     }
     // Stream Size Timers ----------------------------------------
-    // Setup Stream Size Timers, these are small processes that run every 60 seconds and farm metrics for the size of the streams.
-    // If metrics are disabled, these will not run.
+    // Setup Stream Size Timers, these are small processes that run every 30 seconds and record the size of each
+    // stream as its backlog, which the API reports. If metrics are enabled, they also farm metrics for the sizes.
     if role == IngesterRole::BackgroundTaskRunner || role == IngesterRole::All {
         let background_runner_config = config.clone().background_task_runner_config;
         tasks.spawn(background_task_manager.start_runner(background_runner_config));
+
+        if config.cl_items_history_backfill.unwrap_or(false) {
+            tasks.spawn(setup_tree_history_backfill(database_pool.clone()));
+        }
+        if let (Some(true), Some(retention)) =
+            (config.cl_items_history, config.cl_items_history_retention)
+        {
+            tasks.spawn(setup_tree_history_pruning(database_pool.clone(), retention));
+        }
     }
     // Backfiller Setup ------------------------------------------
     if role == IngesterRole::Backfiller || role == IngesterRole::All {
@@ -0,0 +1,92 @@
+use crate::error::IngesterError;
+use anchor_lang::AnchorDeserialize;
+use digital_asset_types::dao::merkle_tree;
+use mpl_bubblegum::accounts::TreeConfig;
+use plerkle_serialization::AccountInfo;
+use sea_orm::{
+    query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection,
+    DbBackend, EntityTrait,
+};
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::state::{
+    merkle_tree_get_size, CompressionAccountType, ConcurrentMerkleTreeHeader,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+
+/// Size of a node stored in the canopy.
+const NODE_SIZE: usize = 32;
+
+pub async fn handle_merkle_tree_account<'a, 'b>(
+    account_update: &'a AccountInfo<'a>,
+    db: &'b DatabaseConnection,
+) -> Result<(), IngesterError> {
+    let key = *account_update.pubkey().unwrap();
+    let data = account_update
+        .data()
+        .map(|data| data.bytes())
+        .unwrap_or_default();
+    save_merkle_tree_header(db, &key.0, data, account_update.slot()).await
+}
+
+/// Caches the shape and latest sequence number of a tree, read from its account data.  Accounts
+/// that do not hold an initialized tree are ignored.
+pub async fn save_merkle_tree_header<T: ConnectionTrait>(
+    conn: &T,
+    tree: &[u8],
+    data: &[u8],
+    slot: u64,
+) -> Result<(), IngesterError> {
+    if data.len() < CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 {
+        return Ok(());
+    }
+    let (mut header_bytes, rest) = data.split_at(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+    let header = ConcurrentMerkleTreeHeader::deserialize(&mut header_bytes)
+        .map_err(|e| IngesterError::DeserializationError(e.to_string()))?;
+    if header.account_type != CompressionAccountType::ConcurrentMerkleTree {
+        return Ok(());
+    }
+    let merkle_tree_size = merkle_tree_get_size(&header)
+        .map_err(|e| IngesterError::DeserializationError(e.to_string()))?;
+    let canopy_nodes = rest.len().saturating_sub(merkle_tree_size) / NODE_SIZE;
+    // The canopy holds the `canopy_depth` levels below the root: 2^(canopy_depth + 1) - 2 nodes.
+    let canopy_depth = (canopy_nodes + 2).ilog2() - 1;
+    // The tree itself starts with its sequence number.
+    let seq = rest
+        .get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes);
+    let tree_key = Pubkey::try_from(tree)
+        .map_err(|_| IngesterError::DeserializationError("Invalid tree key".to_string()))?;
+    let (tree_config, _) = TreeConfig::find_pda(&tree_key);
+
+    let model = merkle_tree::ActiveModel {
+        id: Set(tree.to_vec()),
+        max_depth: Set(header.get_max_depth() as i32),
+        max_buffer_size: Set(header.get_max_buffer_size() as i32),
+        canopy_depth: Set(canopy_depth as i32),
+        slot_updated: Set(slot as i64),
+        seq: Set(seq.map(|seq| seq as i64)),
+        tree_config: Set(Some(tree_config.to_bytes().to_vec())),
+        ..Default::default()
+    };
+    let mut query = merkle_tree::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([merkle_tree::Column::Id])
+                .update_columns([
+                    merkle_tree::Column::MaxDepth,
+                    merkle_tree::Column::MaxBufferSize,
+                    merkle_tree::Column::CanopyDepth,
+                    merkle_tree::Column::SlotUpdated,
+                    merkle_tree::Column::Seq,
+                    merkle_tree::Column::TreeConfig,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= merkle_tree.slot_updated",
+        query.sql
+    );
+    conn.execute(query).await?;
+    Ok(())
+}
@@ -1,12 +1,12 @@
 use digital_asset_types::dao::{
-    asset,
+    asset, notify_asset_transferred, notify_asset_updated,
     sea_orm_active_enums::{
         OwnerType, RoyaltyTargetType, SpecificationAssetClass, SpecificationVersions,
     },
 };
 use sea_orm::{
     sea_query::OnConflict, ConnectionTrait, DbBackend, DbErr, EntityTrait, QueryTrait, Set,
-    TransactionTrait,
+    Statement, TransactionTrait, Values,
 };
 
 pub struct AssetTokenAccountColumns {
@@ -17,12 +17,14 @@ pub struct AssetTokenAccountColumns {
     pub slot_updated_token_account: Option<i64>,
 }
 
+/// With `notify`, the update is announced to subscriptions, see [execute_owner_upsert].
 pub async fn upsert_assets_token_account_columns<T: ConnectionTrait + TransactionTrait>(
     columns: AssetTokenAccountColumns,
     txn_or_conn: &T,
+    notify: bool,
 ) -> Result<(), DbErr> {
     let active_model = asset::ActiveModel {
-        id: Set(columns.mint),
+        id: Set(columns.mint.clone()),
         owner: Set(columns.owner),
         frozen: Set(columns.frozen),
         delegate: Set(columns.delegate),
@@ -45,8 +47,39 @@ pub async fn upsert_assets_token_account_columns<T: ConnectionTrait + Transactio
     query.sql = format!(
     "{} WHERE excluded.slot_updated_token_account >= asset.slot_updated_token_account OR asset.slot_updated_token_account IS NULL",
     query.sql);
-    txn_or_conn.execute(query).await?;
-    Ok(())
+    if notify {
+        execute_owner_upsert(txn_or_conn, &columns.mint, query).await
+    } else {
+        txn_or_conn.execute(query).await.map(|_| ())
+    }
+}
+
+/// Runs an upsert of the owner of an asset and announces the update, along with the owner the
+/// asset had before when it changed hands, so subscribers of that owner hear about the transfer.
+pub async fn execute_owner_upsert<T: ConnectionTrait>(
+    txn_or_conn: &T,
+    id: &[u8],
+    mut query: Statement,
+) -> Result<(), DbErr> {
+    let values = query.values.get_or_insert_with(|| Values(vec![]));
+    values.0.push(id.to_vec().into());
+    // Every part of the statement sees the row as it was before the upsert.
+    query.sql = format!(
+        "WITH previous AS (SELECT owner FROM asset WHERE id = ${}) {} RETURNING asset.owner, (SELECT owner FROM previous) AS previous_owner",
+        values.0.len(),
+        query.sql
+    );
+    let Some(row) = txn_or_conn.query_one(query).await? else {
+        return Ok(());
+    };
+    let owner: Option<Vec<u8>> = row.try_get("", "owner")?;
+    let previous_owner: Option<Vec<u8>> = row.try_get("", "previous_owner")?;
+    match previous_owner {
+        Some(previous_owner) if owner.as_ref() != Some(&previous_owner) => {
+            notify_asset_transferred(txn_or_conn, id, &previous_owner).await
+        }
+        _ => notify_asset_updated(txn_or_conn, id).await,
+    }
 }
 
 pub struct AssetMintAccountColumns {
@@ -61,7 +94,7 @@ pub async fn upsert_assets_mint_account_columns<T: ConnectionTrait + Transaction
     txn_or_conn: &T,
 ) -> Result<(), DbErr> {
     let active_model = asset::ActiveModel {
-        id: Set(columns.mint),
+        id: Set(columns.mint.clone()),
         supply: Set(columns.supply as i64),
         supply_mint: Set(columns.suppply_mint),
         slot_updated_mint_account: Set(Some(columns.slot_updated_mint_account as i64)),
@@ -82,7 +115,9 @@ pub async fn upsert_assets_mint_account_columns<T: ConnectionTrait + Transaction
     query.sql = format!(
     "{} WHERE excluded.slot_updated_mint_account >= asset.slot_updated_mint_account OR asset.slot_updated_mint_account IS NULL",
     query.sql);
-    txn_or_conn.execute(query).await?;
+    if txn_or_conn.execute(query).await?.rows_affected() > 0 {
+        notify_asset_updated(txn_or_conn, &columns.mint).await?;
+    }
     Ok(())
 }
 
@@ -100,7 +135,7 @@ pub async fn upsert_assets_metadata_account_columns<T: ConnectionTrait + Transac
     txn_or_conn: &T,
 ) -> Result<(), DbErr> {
     let active_model = asset::ActiveModel {
-        id: Set(columns.mint),
+        id: Set(columns.mint.clone()),
         owner_type: Set(columns.owner_type),
         specification_version: Set(Some(SpecificationVersions::V1)),
         specification_asset_class: Set(columns.specification_asset_class),
@@ -149,6 +184,8 @@ pub async fn upsert_assets_metadata_account_columns<T: ConnectionTrait + Transac
     query.sql = format!(
         "{} WHERE excluded.slot_updated_metadata_account >= asset.slot_updated_metadata_account OR asset.slot_updated_metadata_account IS NULL",
         query.sql);
-    txn_or_conn.execute(query).await?;
+    if txn_or_conn.execute(query).await?.rows_affected() > 0 {
+        notify_asset_updated(txn_or_conn, &columns.mint).await?;
+    }
     Ok(())
 }
@@ -1,12 +1,13 @@
 use crate::{
     error::IngesterError,
     program_transformers::bubblegum::{
-        save_changelog_event, u32_to_u8_array, upsert_asset_with_seq,
+        instruction_account, save_asset_event, save_changelog_event, u32_to_u8_array,
+        upsert_asset_with_seq,
     },
 };
 use anchor_lang::prelude::Pubkey;
 use blockbuster::{instruction::InstructionBundle, programs::bubblegum::BubblegumInstruction};
-use digital_asset_types::dao::asset;
+use digital_asset_types::dao::{asset, notify_asset_updated, sea_orm_active_enums::AssetEventType};
 use log::debug;
 use sea_orm::{
     entity::*, query::*, sea_query::OnConflict, ConnectionTrait, DbBackend, EntityTrait,
@@ -19,13 +20,22 @@ pub async fn burn<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     if let Some(cl) = &parsing_result.tree_update {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
         let leaf_index = cl.index;
         let (asset_id, _) = Pubkey::find_program_address(
             &[
@@ -58,10 +68,23 @@ where
                     .to_owned(),
             )
             .build(DbBackend::Postgres);
-        multi_txn.execute(query).await?;
+        if multi_txn.execute(query).await?.rows_affected() > 0 {
+            notify_asset_updated(&multi_txn, &id_bytes).await?;
+        }
 
         upsert_asset_with_seq(&multi_txn, id_bytes.to_vec(), seq as i64).await?;
 
+        save_asset_event(
+            &multi_txn,
+            bundle,
+            id_bytes.to_vec(),
+            seq as i64,
+            AssetEventType::Burn,
+            instruction_account(bundle, 1),
+            None,
+        )
+        .await?;
+
         multi_txn.commit().await?;
 
         return Ok(());
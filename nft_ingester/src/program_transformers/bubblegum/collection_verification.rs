@@ -1,8 +1,11 @@
-use crate::program_transformers::bubblegum::{upsert_asset_with_seq, upsert_collection_info};
+use crate::program_transformers::bubblegum::{
+    save_asset_event, upsert_asset_with_seq, upsert_collection_info,
+};
 use blockbuster::{
     instruction::InstructionBundle,
     programs::bubblegum::{BubblegumInstruction, LeafSchema, Payload},
 };
+use digital_asset_types::dao::sea_orm_active_enums::AssetEventType;
 use log::debug;
 use mpl_bubblegum::types::Collection;
 use sea_orm::query::*;
@@ -15,6 +18,7 @@ pub async fn process<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -38,8 +42,16 @@ where
             "Handling collection verification event for {} (verify: {}): {}",
             collection, verify, bundle.txn_id
         );
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
         let id_bytes = match le.schema {
             LeafSchema::V1 { id, .. } => id.to_bytes().to_vec(),
         };
@@ -79,6 +91,22 @@ where
         )
         .await?;
 
+        let event_type = if *verify {
+            AssetEventType::VerifyCollection
+        } else {
+            AssetEventType::UnverifyCollection
+        };
+        save_asset_event(
+            &multi_txn,
+            bundle,
+            id_bytes.to_vec(),
+            seq as i64,
+            event_type,
+            None,
+            Some(collection.to_bytes().to_vec()),
+        )
+        .await?;
+
         multi_txn.commit().await?;
 
         return Ok(());
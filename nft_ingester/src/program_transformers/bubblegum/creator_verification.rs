@@ -1,7 +1,7 @@
 use crate::{
     error::IngesterError,
     program_transformers::bubblegum::{
-        save_changelog_event, upsert_asset_creators, upsert_asset_with_leaf_info,
+        save_asset_event, save_changelog_event, upsert_asset_creators, upsert_asset_with_leaf_info,
         upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
     },
 };
@@ -9,6 +9,7 @@ use blockbuster::{
     instruction::InstructionBundle,
     programs::bubblegum::{BubblegumInstruction, LeafSchema, Payload},
 };
+use digital_asset_types::dao::sea_orm_active_enums::AssetEventType;
 use log::debug;
 use mpl_bubblegum::types::Creator;
 use sea_orm::{ConnectionTrait, TransactionTrait};
@@ -19,6 +20,7 @@ pub async fn process<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -58,8 +60,16 @@ where
             "Handling creator verification event for creator {} (verify: {}): {}",
             creator, verify, bundle.txn_id
         );
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
 
         match le.schema {
             LeafSchema::V1 {
@@ -119,6 +129,22 @@ where
                 )
                 .await?;
 
+                let event_type = if *verify {
+                    AssetEventType::VerifyCreator
+                } else {
+                    AssetEventType::UnverifyCreator
+                };
+                save_asset_event(
+                    &multi_txn,
+                    bundle,
+                    id_bytes.to_vec(),
+                    seq as i64,
+                    event_type,
+                    None,
+                    Some(creator.to_bytes().to_vec()),
+                )
+                .await?;
+
                 multi_txn.commit().await?;
             }
         };
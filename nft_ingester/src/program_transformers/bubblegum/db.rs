@@ -1,9 +1,10 @@
-use crate::error::IngesterError;
+use crate::{error::IngesterError, program_transformers::asset_upserts::execute_owner_upsert};
+use blockbuster::instruction::InstructionBundle;
 use digital_asset_types::dao::{
-    asset, asset_authority, asset_creators, asset_data, asset_grouping, backfill_items,
-    cl_audits_v2, cl_items,
+    asset, asset_authority, asset_creators, asset_data, asset_events, asset_grouping,
+    backfill_items, cl_audits_v2, cl_items, cl_items_history, notify_asset_updated,
     sea_orm_active_enums::{
-        ChainMutability, Instruction, Mutability, OwnerType, RoyaltyTargetType,
+        AssetEventType, ChainMutability, Instruction, Mutability, OwnerType, RoyaltyTargetType,
         SpecificationAssetClass, SpecificationVersions,
     },
 };
@@ -21,14 +22,64 @@ pub async fn save_changelog_event<'c, T>(
     txn: &T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<u64, IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
-    insert_change_log(change_log_event, slot, txn_id, txn, instruction, cl_audits).await?;
+    insert_change_log(
+        change_log_event,
+        slot,
+        txn_id,
+        txn,
+        instruction,
+        cl_audits,
+        cl_items_history,
+    )
+    .await?;
     Ok(change_log_event.seq)
 }
 
+/// Records a change made to an asset by a bubblegum instruction in the asset's history.
+pub async fn save_asset_event<T>(
+    txn: &T,
+    bundle: &InstructionBundle<'_>,
+    asset_id: Vec<u8>,
+    seq: i64,
+    event_type: AssetEventType,
+    from_address: Option<Vec<u8>>,
+    to_address: Option<Vec<u8>>,
+) -> Result<(), IngesterError>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let tx = bs58::decode(bundle.txn_id)
+        .into_vec()
+        .map_err(|_| IngesterError::ChangeLogEventMalformed)?;
+    let event = asset_events::ActiveModel {
+        asset_id: Set(asset_id),
+        event_type: Set(event_type),
+        seq: Set(seq),
+        slot: Set(bundle.slot as i64),
+        tx: Set(tx),
+        from_address: Set(from_address),
+        to_address: Set(to_address),
+        ..Default::default()
+    };
+    let query = asset_events::Entity::insert(event)
+        .on_conflict(
+            OnConflict::columns([asset_events::Column::AssetId, asset_events::Column::Seq])
+                .do_nothing()
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    txn.execute(query)
+        .await
+        .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+
+    Ok(())
+}
+
 const fn node_idx_to_leaf_idx(index: i64, tree_height: u32) -> i64 {
     index - 2i64.pow(tree_height)
 }
@@ -40,6 +91,7 @@ pub async fn insert_change_log<'c, T>(
     txn: &T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -91,6 +143,32 @@ where
         txn.execute(query)
             .await
             .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+
+        // Keep every version of the node so proofs can be rebuilt at past sequence numbers.
+        if cl_items_history {
+            let history_item = cl_items_history::ActiveModel {
+                tree: Set(tree_id.to_vec()),
+                level: Set(i - 1),
+                node_idx: Set(node_idx),
+                hash: Set(p.node.as_ref().to_vec()),
+                seq: Set(change_log_event.seq as i64),
+                ..Default::default()
+            };
+            let query = cl_items_history::Entity::insert(history_item)
+                .on_conflict(
+                    OnConflict::columns([
+                        cl_items_history::Column::Tree,
+                        cl_items_history::Column::NodeIdx,
+                        cl_items_history::Column::Seq,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .build(DbBackend::Postgres);
+            txn.execute(query)
+                .await
+                .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+        }
     }
 
     // Insert the audit item after the insert into cl_items have been completed
@@ -108,6 +186,7 @@ where
             seq: Set(change_log_event.seq as i64),
             tx: Set(tx_id_bytes),
             instruction: Set(ix),
+            slot: Set(Some(slot as i64)),
             ..Default::default()
         };
         let query = cl_audits_v2::Entity::insert(audit_item_v2)
@@ -180,7 +259,7 @@ where
     let data_hash = bs58::encode(data_hash).into_string().trim().to_string();
     let creator_hash = bs58::encode(creator_hash).into_string().trim().to_string();
     let model = asset::ActiveModel {
-        id: Set(id),
+        id: Set(id.clone()),
         nonce: Set(Some(nonce)),
         tree_id: Set(Some(tree_id)),
         leaf: Set(Some(leaf)),
@@ -212,9 +291,13 @@ where
         query.sql
     );
 
-    txn.execute(query)
+    let result = txn
+        .execute(query)
         .await
         .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+    if result.rows_affected() > 0 {
+        notify_asset_updated(txn, &id).await?;
+    }
 
     Ok(())
 }
@@ -230,7 +313,7 @@ where
     T: ConnectionTrait + TransactionTrait,
 {
     let model = asset::ActiveModel {
-        id: Set(id),
+        id: Set(id.clone()),
         owner: Set(Some(owner)),
         delegate: Set(delegate),
         owner_delegate_seq: Set(Some(seq)),
@@ -256,11 +339,9 @@ where
             query.sql
         );
 
-    txn.execute(query)
+    execute_owner_upsert(txn, &id, query)
         .await
-        .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
-
-    Ok(())
+        .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))
 }
 
 pub async fn upsert_asset_with_compression_info<T>(
@@ -275,7 +356,7 @@ where
     T: ConnectionTrait + TransactionTrait,
 {
     let model = asset::ActiveModel {
-        id: Set(id),
+        id: Set(id.clone()),
         compressed: Set(compressed),
         compressible: Set(compressible),
         supply: Set(supply),
@@ -298,7 +379,10 @@ where
 
     // Do not overwrite changes that happened after decompression (asset.seq = 0).
     query.sql = format!("{} WHERE asset.seq != 0 OR asset.seq IS NULL", query.sql);
-    txn.execute(query).await?;
+    let result = txn.execute(query).await?;
+    if result.rows_affected() > 0 {
+        notify_asset_updated(txn, &id).await?;
+    }
 
     Ok(())
 }
@@ -351,7 +435,7 @@ where
     };
 
     let model = asset_grouping::ActiveModel {
-        asset_id: Set(asset_id),
+        asset_id: Set(asset_id.clone()),
         group_key: Set("collection".to_string()),
         group_value: Set(group_value),
         verified: Set(verified),
@@ -382,9 +466,13 @@ where
         query.sql
     );
 
-    txn.execute(query)
+    let result = txn
+        .execute(query)
         .await
         .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+    if result.rows_affected() > 0 {
+        notify_asset_updated(txn, &asset_id).await?;
+    }
 
     Ok(())
 }
@@ -448,9 +536,13 @@ where
         "{} WHERE (asset_data.base_info_seq != 0 AND excluded.base_info_seq >= asset_data.base_info_seq) OR asset_data.base_info_seq IS NULL",
         query.sql
     );
-    txn.execute(query)
+    let result = txn
+        .execute(query)
         .await
         .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+    if result.rows_affected() > 0 {
+        notify_asset_updated(txn, &id).await?;
+    }
 
     Ok(())
 }
@@ -512,9 +604,13 @@ where
             query.sql
         );
 
-    txn.execute(query)
+    let result = txn
+        .execute(query)
         .await
         .map_err(|db_err| IngesterError::AssetIndexError(db_err.to_string()))?;
+    if result.rows_affected() > 0 {
+        notify_asset_updated(txn, &id).await?;
+    }
 
     Ok(())
 }
@@ -583,7 +679,10 @@ where
         query.sql
     );
 
-    txn.execute(query).await?;
+    let result = txn.execute(query).await?;
+    if result.rows_affected() > 0 {
+        notify_asset_updated(txn, &id).await?;
+    }
 
     Ok(())
 }
@@ -599,7 +698,7 @@ where
     T: ConnectionTrait + TransactionTrait,
 {
     let model = asset_authority::ActiveModel {
-        asset_id: Set(asset_id),
+        asset_id: Set(asset_id.clone()),
         authority: Set(authority),
         seq: Set(seq),
         slot_updated: Set(slot_updated),
@@ -617,9 +716,13 @@ where
         )
         .build(DbBackend::Postgres);
 
-    txn.execute(query)
+    let result = txn
+        .execute(query)
         .await
         .map_err(|db_err| IngesterError::AssetIndexError(db_err.to_string()))?;
+    if result.rows_affected() > 0 {
+        notify_asset_updated(txn, &asset_id).await?;
+    }
 
     Ok(())
 }
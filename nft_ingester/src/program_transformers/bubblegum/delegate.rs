@@ -1,7 +1,7 @@
 use crate::{
     error::IngesterError,
     program_transformers::bubblegum::{
-        save_changelog_event, upsert_asset_with_leaf_info,
+        instruction_account, save_asset_event, save_changelog_event, upsert_asset_with_leaf_info,
         upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
     },
 };
@@ -9,6 +9,7 @@ use blockbuster::{
     instruction::InstructionBundle,
     programs::bubblegum::{BubblegumInstruction, LeafSchema},
 };
+use digital_asset_types::dao::sea_orm_active_enums::AssetEventType;
 use sea_orm::{ConnectionTrait, TransactionTrait};
 
 pub async fn delegate<'c, T>(
@@ -17,13 +18,22 @@ pub async fn delegate<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     if let (Some(le), Some(cl)) = (&parsing_result.leaf_update, &parsing_result.tree_update) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
         match le.schema {
             LeafSchema::V1 {
                 id,
@@ -39,6 +49,14 @@ where
                     Some(delegate.to_bytes().to_vec())
                 };
                 let tree_id = cl.id.to_bytes();
+                let previous_delegate = instruction_account(bundle, 2)
+                    .filter(|previous_delegate| previous_delegate.as_slice() != owner.as_ref());
+                let event_type = if delegate.is_some() {
+                    AssetEventType::Delegate
+                } else {
+                    AssetEventType::Undelegate
+                };
+                let new_delegate = delegate.clone();
 
                 // Begin a transaction.  If the transaction goes out of scope (i.e. one of the executions has
                 // an error and this function returns it using the `?` operator), then the transaction is
@@ -70,6 +88,17 @@ where
 
                 upsert_asset_with_seq(&multi_txn, id_bytes.to_vec(), seq as i64).await?;
 
+                save_asset_event(
+                    &multi_txn,
+                    bundle,
+                    id_bytes.to_vec(),
+                    seq as i64,
+                    event_type,
+                    previous_delegate,
+                    new_delegate,
+                )
+                .await?;
+
                 multi_txn.commit().await?;
 
                 return Ok(());
@@ -1,8 +1,8 @@
 use crate::{
     error::IngesterError,
     program_transformers::bubblegum::{
-        bgum_use_method_to_token_metadata_use_method, save_changelog_event, upsert_asset_authority,
-        upsert_asset_base_info, upsert_asset_creators, upsert_asset_data,
+        bgum_use_method_to_token_metadata_use_method, save_asset_event, save_changelog_event,
+        upsert_asset_authority, upsert_asset_base_info, upsert_asset_creators, upsert_asset_data,
         upsert_asset_with_compression_info, upsert_asset_with_leaf_info,
         upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq, upsert_collection_info,
     },
@@ -16,8 +16,8 @@ use blockbuster::{
 use chrono::Utc;
 use digital_asset_types::{
     dao::sea_orm_active_enums::{
-        ChainMutability, Mutability, OwnerType, RoyaltyTargetType, SpecificationAssetClass,
-        SpecificationVersions,
+        AssetEventType, ChainMutability, Mutability, OwnerType, RoyaltyTargetType,
+        SpecificationAssetClass, SpecificationVersions,
     },
     json::ChainDataV1,
 };
@@ -30,6 +30,7 @@ pub async fn mint_v1<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<Option<TaskData>, IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -47,8 +48,16 @@ where
         &parsing_result.tree_update,
         &parsing_result.payload,
     ) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
         let metadata = args;
         #[allow(unreachable_patterns)]
         return match le.schema {
@@ -195,6 +204,17 @@ where
                 )
                 .await?;
 
+                save_asset_event(
+                    &multi_txn,
+                    bundle,
+                    id_bytes.to_vec(),
+                    seq as i64,
+                    AssetEventType::Mint,
+                    None,
+                    Some(owner.to_bytes().to_vec()),
+                )
+                .await?;
+
                 multi_txn.commit().await?;
 
                 if uri.is_empty() {
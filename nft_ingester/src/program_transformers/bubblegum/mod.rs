@@ -17,9 +17,11 @@ mod delegate;
 mod mint_v1;
 mod redeem;
 mod transfer;
+mod tree_config;
 mod update_metadata;
 
 pub use db::*;
+pub use tree_config::handle_tree_config_account;
 
 use crate::{error::IngesterError, tasks::TaskData};
 
@@ -29,6 +31,7 @@ pub async fn handle_bubblegum_instruction<'c, T>(
     txn: &T,
     task_manager: &UnboundedSender<TaskData>,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -61,44 +64,116 @@ where
 
     match ix_type {
         InstructionName::Transfer => {
-            transfer::transfer(parsing_result, bundle, txn, ix_str, cl_audits).await?;
+            transfer::transfer(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
         }
         InstructionName::Burn => {
-            burn::burn(parsing_result, bundle, txn, ix_str, cl_audits).await?;
+            burn::burn(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
         }
         InstructionName::Delegate => {
-            delegate::delegate(parsing_result, bundle, txn, ix_str, cl_audits).await?;
+            delegate::delegate(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
         }
         InstructionName::MintV1 | InstructionName::MintToCollectionV1 => {
-            let task = mint_v1::mint_v1(parsing_result, bundle, txn, ix_str, cl_audits).await?;
+            let task = mint_v1::mint_v1(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
 
             if let Some(t) = task {
                 task_manager.send(t)?;
             }
         }
         InstructionName::Redeem => {
-            redeem::redeem(parsing_result, bundle, txn, ix_str, cl_audits).await?;
+            redeem::redeem(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
         }
         InstructionName::CancelRedeem => {
-            cancel_redeem::cancel_redeem(parsing_result, bundle, txn, ix_str, cl_audits).await?;
+            cancel_redeem::cancel_redeem(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
         }
         InstructionName::DecompressV1 => {
             debug!("No action necessary for decompression")
         }
         InstructionName::VerifyCreator | InstructionName::UnverifyCreator => {
-            creator_verification::process(parsing_result, bundle, txn, ix_str, cl_audits).await?;
+            creator_verification::process(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
         }
         InstructionName::VerifyCollection
         | InstructionName::UnverifyCollection
         | InstructionName::SetAndVerifyCollection => {
-            collection_verification::process(parsing_result, bundle, txn, ix_str, cl_audits)
-                .await?;
+            collection_verification::process(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
+        }
+        InstructionName::CreateTree => {
+            tree_config::create_tree(bundle, txn).await?;
         }
         InstructionName::SetDecompressibleState => (), // Nothing to index.
         InstructionName::UpdateMetadata => {
-            let task =
-                update_metadata::update_metadata(parsing_result, bundle, txn, ix_str, cl_audits)
-                    .await?;
+            let task = update_metadata::update_metadata(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_audits,
+                cl_items_history,
+            )
+            .await?;
 
             if let Some(t) = task {
                 task_manager.send(t)?;
@@ -109,6 +184,11 @@ where
     Ok(())
 }
 
+// Account at `index` in the accounts of the instruction.
+fn instruction_account(bundle: &InstructionBundle, index: usize) -> Option<Vec<u8>> {
+    bundle.keys.get(index).map(|key| key.0.to_vec())
+}
+
 // PDA lookup requires an 8-byte array.
 fn u32_to_u8_array(value: u32) -> [u8; 8] {
     let bytes: [u8; 4] = value.to_le_bytes();
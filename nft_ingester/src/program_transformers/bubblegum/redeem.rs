@@ -4,10 +4,12 @@ use log::debug;
 use crate::{
     error::IngesterError,
     program_transformers::bubblegum::{
-        save_changelog_event, u32_to_u8_array, upsert_asset_with_leaf_info, upsert_asset_with_seq,
+        instruction_account, save_asset_event, save_changelog_event, u32_to_u8_array,
+        upsert_asset_with_leaf_info, upsert_asset_with_seq,
     },
 };
 use blockbuster::{instruction::InstructionBundle, programs::bubblegum::BubblegumInstruction};
+use digital_asset_types::dao::sea_orm_active_enums::AssetEventType;
 use sea_orm::{ConnectionTrait, TransactionTrait};
 
 pub async fn redeem<'c, T>(
@@ -16,13 +18,22 @@ pub async fn redeem<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     if let Some(cl) = &parsing_result.tree_update {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
         let leaf_index = cl.index;
         let (asset_id, _) = Pubkey::find_program_address(
             &[
@@ -57,6 +68,17 @@ where
 
         upsert_asset_with_seq(&multi_txn, id_bytes.to_vec(), seq as i64).await?;
 
+        save_asset_event(
+            &multi_txn,
+            bundle,
+            id_bytes.to_vec(),
+            seq as i64,
+            AssetEventType::Redeem,
+            instruction_account(bundle, 1),
+            None,
+        )
+        .await?;
+
         multi_txn.commit().await?;
 
         return Ok(());
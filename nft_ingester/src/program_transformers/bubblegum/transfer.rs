@@ -2,14 +2,15 @@ use super::save_changelog_event;
 use crate::{
     error::IngesterError,
     program_transformers::bubblegum::{
-        upsert_asset_with_leaf_info, upsert_asset_with_owner_and_delegate_info,
-        upsert_asset_with_seq,
+        instruction_account, save_asset_event, upsert_asset_with_leaf_info,
+        upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
     },
 };
 use blockbuster::{
     instruction::InstructionBundle,
     programs::bubblegum::{BubblegumInstruction, LeafSchema},
 };
+use digital_asset_types::dao::sea_orm_active_enums::AssetEventType;
 use sea_orm::{ConnectionTrait, TransactionTrait};
 
 pub async fn transfer<'c, T>(
@@ -18,13 +19,22 @@ pub async fn transfer<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<(), IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     if let (Some(le), Some(cl)) = (&parsing_result.leaf_update, &parsing_result.tree_update) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
         match le.schema {
             LeafSchema::V1 {
                 id,
@@ -72,6 +82,17 @@ where
 
                 upsert_asset_with_seq(&multi_txn, id_bytes.to_vec(), seq as i64).await?;
 
+                save_asset_event(
+                    &multi_txn,
+                    bundle,
+                    id_bytes.to_vec(),
+                    seq as i64,
+                    AssetEventType::Transfer,
+                    instruction_account(bundle, 1),
+                    Some(owner.to_bytes().to_vec()),
+                )
+                .await?;
+
                 multi_txn.commit().await?;
 
                 return Ok(());
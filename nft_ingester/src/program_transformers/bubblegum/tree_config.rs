@@ -0,0 +1,135 @@
+use crate::error::IngesterError;
+use blockbuster::instruction::InstructionBundle;
+use borsh::BorshDeserialize;
+use digital_asset_types::dao::merkle_tree;
+use log::debug;
+use mpl_bubblegum::{accounts::TreeConfig, instructions::CreateTreeConfigInstructionArgs};
+use plerkle_serialization::AccountInfo;
+use sea_orm::{
+    query::*, sea_query::Expr, sea_query::OnConflict, ActiveValue::Set, ColumnTrait,
+    ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+};
+use solana_sdk::hash::hashv;
+
+/// Records the creator and capacity of a tree from its `CreateTree` instruction.  The tree shape
+/// is taken from the instruction arguments until the tree account itself is indexed, which is
+/// also when the canopy depth becomes known.
+pub async fn create_tree<'c, T>(
+    bundle: &InstructionBundle<'c>,
+    txn: &'c T,
+) -> Result<(), IngesterError>
+where
+    T: ConnectionTrait,
+{
+    let (Some(tree_config), Some(tree), Some(tree_creator)) =
+        (bundle.keys.first(), bundle.keys.get(1), bundle.keys.get(3))
+    else {
+        return Err(IngesterError::DeserializationError(
+            "CreateTree is missing accounts".to_string(),
+        ));
+    };
+    let data = bundle
+        .instruction
+        .and_then(|ix| ix.data())
+        .map(|data| data.bytes())
+        .unwrap_or_default();
+    let args = data
+        .get(8..)
+        .and_then(|mut args| CreateTreeConfigInstructionArgs::deserialize(&mut args).ok())
+        .ok_or_else(|| {
+            IngesterError::DeserializationError("Invalid CreateTree arguments".to_string())
+        })?;
+
+    let model = merkle_tree::ActiveModel {
+        id: Set(tree.0.to_vec()),
+        max_depth: Set(args.max_depth as i32),
+        max_buffer_size: Set(args.max_buffer_size as i32),
+        canopy_depth: Set(0),
+        // Lets the first tree account update overwrite the shape.
+        slot_updated: Set(0),
+        seq: Set(None),
+        tree_config: Set(Some(tree_config.0.to_vec())),
+        tree_creator: Set(Some(tree_creator.0.to_vec())),
+        tree_delegate: Set(Some(tree_creator.0.to_vec())),
+        is_public: Set(Some(args.public.unwrap_or(false))),
+        num_minted: Set(Some(0)),
+        total_mint_capacity: Set(Some(1i64 << args.max_depth)),
+        config_slot_updated: Set(Some(bundle.slot as i64)),
+    };
+    let mut query = merkle_tree::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([merkle_tree::Column::Id])
+                .update_columns([
+                    merkle_tree::Column::TreeConfig,
+                    merkle_tree::Column::TreeCreator,
+                    merkle_tree::Column::TreeDelegate,
+                    merkle_tree::Column::IsPublic,
+                    merkle_tree::Column::NumMinted,
+                    merkle_tree::Column::TotalMintCapacity,
+                    merkle_tree::Column::ConfigSlotUpdated,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    query.sql = format!(
+        "{} WHERE merkle_tree.config_slot_updated IS NULL OR excluded.config_slot_updated >= merkle_tree.config_slot_updated",
+        query.sql
+    );
+    txn.execute(query)
+        .await
+        .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+    Ok(())
+}
+
+/// Updates the tree that a bubblegum tree config account belongs to.  The account does not name
+/// its tree, so updates for trees that have not been indexed yet are dropped; the config is
+/// rewritten on every mint, so it catches up once the tree is known.
+pub async fn handle_tree_config_account<'a, 'b>(
+    account_update: &'a AccountInfo<'a>,
+    db: &'b DatabaseConnection,
+) -> Result<(), IngesterError> {
+    let key = *account_update.pubkey().unwrap();
+    let data = account_update
+        .data()
+        .map(|data| data.bytes())
+        .unwrap_or_default();
+    // Bubblegum also owns vouchers, which are told apart by their discriminator.
+    let discriminator = &hashv(&[b"account:TreeConfig"]).to_bytes()[..8];
+    if data.len() < TreeConfig::LEN || &data[..8] != discriminator {
+        debug!("Bubblegum account {:?} is not a tree config", key);
+        return Ok(());
+    }
+    let config = TreeConfig::from_bytes(data)
+        .map_err(|e| IngesterError::DeserializationError(e.to_string()))?;
+    let slot = account_update.slot() as i64;
+
+    merkle_tree::Entity::update_many()
+        .col_expr(
+            merkle_tree::Column::TreeCreator,
+            Expr::value(config.tree_creator.to_bytes().to_vec()),
+        )
+        .col_expr(
+            merkle_tree::Column::TreeDelegate,
+            Expr::value(config.tree_delegate.to_bytes().to_vec()),
+        )
+        .col_expr(merkle_tree::Column::IsPublic, Expr::value(config.is_public))
+        .col_expr(
+            merkle_tree::Column::NumMinted,
+            Expr::value(config.num_minted as i64),
+        )
+        .col_expr(
+            merkle_tree::Column::TotalMintCapacity,
+            Expr::value(config.total_mint_capacity as i64),
+        )
+        .col_expr(merkle_tree::Column::ConfigSlotUpdated, Expr::value(slot))
+        .filter(merkle_tree::Column::TreeConfig.eq(key.0.to_vec()))
+        .filter(
+            Condition::any()
+                .add(merkle_tree::Column::ConfigSlotUpdated.is_null())
+                .add(merkle_tree::Column::ConfigSlotUpdated.lte(slot)),
+        )
+        .exec(db)
+        .await
+        .map_err(|db_err| IngesterError::StorageWriteError(db_err.to_string()))?;
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use crate::{
     error::IngesterError,
     program_transformers::bubblegum::{
-        bgum_use_method_to_token_metadata_use_method, save_changelog_event, upsert_asset_base_info,
-        upsert_asset_creators, upsert_asset_data, upsert_asset_with_leaf_info,
-        upsert_asset_with_seq,
+        bgum_use_method_to_token_metadata_use_method, save_asset_event, save_changelog_event,
+        upsert_asset_base_info, upsert_asset_creators, upsert_asset_data,
+        upsert_asset_with_leaf_info, upsert_asset_with_seq,
     },
     tasks::{DownloadMetadata, IntoTaskData, TaskData},
 };
@@ -15,8 +15,8 @@ use blockbuster::{
 use chrono::Utc;
 use digital_asset_types::{
     dao::sea_orm_active_enums::{
-        ChainMutability, Mutability, OwnerType, RoyaltyTargetType, SpecificationAssetClass,
-        SpecificationVersions,
+        AssetEventType, ChainMutability, Mutability, OwnerType, RoyaltyTargetType,
+        SpecificationAssetClass, SpecificationVersions,
     },
     json::ChainDataV1,
 };
@@ -29,6 +29,7 @@ pub async fn update_metadata<'c, T>(
     txn: &'c T,
     instruction: &str,
     cl_audits: bool,
+    cl_items_history: bool,
 ) -> Result<Option<TaskData>, IngesterError>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -46,8 +47,16 @@ where
         &parsing_result.tree_update,
         &parsing_result.payload,
     ) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction, cl_audits)
-            .await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_audits,
+            cl_items_history,
+        )
+        .await?;
 
         #[allow(unreachable_patterns)]
         return match le.schema {
@@ -177,6 +186,17 @@ where
                 upsert_asset_creators(&multi_txn, id_bytes.to_vec(), creators, slot_i, seq as i64)
                     .await?;
 
+                save_asset_event(
+                    &multi_txn,
+                    bundle,
+                    id_bytes.to_vec(),
+                    seq as i64,
+                    AssetEventType::UpdateMetadata,
+                    None,
+                    None,
+                )
+                .await?;
+
                 multi_txn.commit().await?;
 
                 if uri.is_empty() {
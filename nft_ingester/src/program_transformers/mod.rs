@@ -10,31 +10,52 @@ use blockbuster::{
 use log::{debug, error, info};
 use plerkle_serialization::{AccountInfo, Pubkey as FBPubkey, TransactionInfo};
 use sea_orm::{DatabaseConnection, SqlxPostgresConnector};
-use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{pubkey, pubkey::Pubkey};
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet, VecDeque};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::program_transformers::{
-    bubblegum::handle_bubblegum_instruction, token::handle_token_program_account,
-    token_metadata::handle_token_metadata_account,
+    account_compression::handle_merkle_tree_account,
+    bubblegum::{handle_bubblegum_instruction, handle_tree_config_account},
+    token::handle_token_program_account,
+    token_metadata::{handle_token_metadata_account, save_asset_signatures},
 };
 
+mod account_compression;
 mod asset_upserts;
 mod bubblegum;
 mod token;
 mod token_metadata;
 
+pub use account_compression::save_merkle_tree_header;
+
+const SPL_TOKEN_2022_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+fn is_token_program(key: &Pubkey) -> bool {
+    *key == blockbuster::token_metadata::ID || *key == spl_token::id() || *key == SPL_TOKEN_2022_ID
+}
+
 pub struct ProgramTransformer {
     storage: DatabaseConnection,
     task_sender: UnboundedSender<TaskData>,
     matchers: HashMap<Pubkey, Box<dyn ProgramParser>>,
     key_set: HashSet<Pubkey>,
     cl_audits: bool,
+    cl_items_history: bool,
+    asset_signatures: bool,
+    token_account_updates: bool,
 }
 
 impl ProgramTransformer {
-    pub fn new(pool: PgPool, task_sender: UnboundedSender<TaskData>, cl_audits: bool) -> Self {
+    pub fn new(
+        pool: PgPool,
+        task_sender: UnboundedSender<TaskData>,
+        cl_audits: bool,
+        cl_items_history: bool,
+        asset_signatures: bool,
+        token_account_updates: bool,
+    ) -> Self {
         let mut matchers: HashMap<Pubkey, Box<dyn ProgramParser>> = HashMap::with_capacity(1);
         let bgum = BubblegumParser {};
         let token_metadata = TokenMetadataParser {};
@@ -42,10 +63,14 @@ impl ProgramTransformer {
         matchers.insert(bgum.key(), Box::new(bgum));
         matchers.insert(token_metadata.key(), Box::new(token_metadata));
         matchers.insert(token.key(), Box::new(token));
-        let hs = matchers.iter().fold(HashSet::new(), |mut acc, (k, _)| {
+        let mut hs = matchers.iter().fold(HashSet::new(), |mut acc, (k, _)| {
             acc.insert(*k);
             acc
         });
+        // Token-2022 has no parser, its instructions are only read for the accounts they touch.
+        if asset_signatures {
+            hs.insert(SPL_TOKEN_2022_ID);
+        }
         let pool: PgPool = pool;
         ProgramTransformer {
             storage: SqlxPostgresConnector::from_sqlx_postgres_pool(pool),
@@ -53,6 +78,9 @@ impl ProgramTransformer {
             matchers,
             key_set: hs,
             cl_audits,
+            cl_items_history,
+            asset_signatures,
+            token_account_updates,
         }
     }
 
@@ -89,6 +117,9 @@ impl ProgramTransformer {
         for k in accounts.into_iter() {
             keys.push(*k);
         }
+        // Accounts passed to Token Metadata and SPL Token instructions, used to record the
+        // transaction in the history of the uncompressed assets it touches.
+        let mut token_ix_keys: HashSet<Pubkey> = HashSet::new();
         let mut not_impl = 0;
         let ixlen = instructions.len();
         debug!("Instructions: {}", ixlen);
@@ -123,6 +154,16 @@ impl ProgramTransformer {
                 keys: ix_accounts.as_slice(),
                 slot,
             };
+            let token_ix = self.asset_signatures
+                && Pubkey::try_from(ix.program.0.as_slice())
+                    .is_ok_and(|key| is_token_program(&key));
+            if token_ix {
+                token_ix_keys.extend(
+                    ix.keys
+                        .iter()
+                        .filter_map(|key| Pubkey::try_from(key.0.as_slice()).ok()),
+                );
+            }
 
             if let Some(program) = self.match_program(&ix.program) {
                 debug!("Found a ix for program: {:?}", program.key());
@@ -136,6 +177,7 @@ impl ProgramTransformer {
                             &self.storage,
                             &self.task_sender,
                             self.cl_audits,
+                            self.cl_items_history,
                         )
                         .await
                         .map_err(|err| {
@@ -146,6 +188,7 @@ impl ProgramTransformer {
                             err
                         })?;
                     }
+                    _ if token_ix => {}
                     _ => {
                         not_impl += 1;
                     }
@@ -153,6 +196,18 @@ impl ProgramTransformer {
             }
         }
 
+        if !token_ix_keys.is_empty() {
+            save_asset_signatures(&self.storage, &token_ix_keys, txn_id, slot)
+                .await
+                .map_err(|err| {
+                    error!(
+                        "Failed to save asset signatures for txn {:?}: {:?}",
+                        sig, err
+                    );
+                    err
+                })?;
+        }
+
         if not_impl == ixlen {
             debug!("Not imple");
             return Err(IngesterError::NotImplemented);
@@ -165,6 +220,13 @@ impl ProgramTransformer {
         acct: AccountInfo<'b>,
     ) -> Result<(), IngesterError> {
         let owner = acct.owner().unwrap();
+        // Tree and tree config accounts are read directly, there is no parser for them.
+        if owner.0 == spl_account_compression::id().to_bytes() {
+            return handle_merkle_tree_account(&acct, &self.storage).await;
+        }
+        if owner.0 == mpl_bubblegum::ID.to_bytes() {
+            return handle_tree_config_account(&acct, &self.storage).await;
+        }
         if let Some(program) = self.match_program(owner) {
             let result = program.handle_account(&acct)?;
             let concrete = result.result_type();
@@ -184,6 +246,7 @@ impl ProgramTransformer {
                         parsing_result,
                         &self.storage,
                         &self.task_sender,
+                        self.token_account_updates,
                     )
                     .await
                 }
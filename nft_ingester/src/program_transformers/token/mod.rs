@@ -20,6 +20,7 @@ pub async fn handle_token_program_account<'a, 'b, 'c>(
     parsing_result: &'b TokenProgramAccount,
     db: &'c DatabaseConnection,
     _task_manager: &UnboundedSender<TaskData>,
+    token_account_updates: bool,
 ) -> Result<(), IngesterError> {
     let key = *account_update.pubkey().unwrap();
     let key_bytes = key.0.to_vec();
@@ -87,6 +88,7 @@ pub async fn handle_token_program_account<'a, 'b, 'c>(
                             slot_updated_token_account: Some(account_update.slot() as i64),
                         },
                         &txn,
+                        token_account_updates,
                     )
                     .await?;
                 }
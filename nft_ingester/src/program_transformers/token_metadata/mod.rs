@@ -1,4 +1,5 @@
 mod master_edition;
+mod signatures;
 mod v1_asset;
 
 use crate::{
@@ -14,6 +15,8 @@ use plerkle_serialization::AccountInfo;
 use sea_orm::{DatabaseConnection, TransactionTrait};
 use tokio::sync::mpsc::UnboundedSender;
 
+pub use signatures::save_asset_signatures;
+
 pub async fn handle_token_metadata_account<'a, 'b, 'c>(
     account_update: &'a AccountInfo<'a>,
     parsing_result: &'b TokenMetadataAccountState,
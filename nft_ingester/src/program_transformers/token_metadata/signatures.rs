@@ -0,0 +1,90 @@
+use crate::error::IngesterError;
+use digital_asset_types::dao::{
+    asset, asset_signatures, sea_orm_active_enums::SpecificationAssetClass, token_accounts, tokens,
+};
+use sea_orm::{
+    entity::*, query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DbBackend,
+    EntityTrait,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+
+/// Records `signature` in the history of every NFT whose mint or token accounts are passed to one
+/// of the Token Metadata or SPL Token instructions of the transaction. Only mints indexed as NFTs
+/// are recorded, which leaves out fungible tokens.
+pub async fn save_asset_signatures<T: ConnectionTrait>(
+    conn: &T,
+    ix_keys: &HashSet<Pubkey>,
+    signature: &str,
+    slot: u64,
+) -> Result<(), IngesterError> {
+    // Token accounts stand in for their mints.
+    let keys: Vec<Vec<u8>> = ix_keys.iter().map(|key| key.to_bytes().to_vec()).collect();
+    let indexed_token_accounts = token_accounts::Entity::find()
+        .filter(token_accounts::Column::Pubkey.is_in(keys.clone()))
+        .all(conn)
+        .await?;
+    let candidates: HashSet<Vec<u8>> = keys
+        .into_iter()
+        .chain(
+            indexed_token_accounts
+                .into_iter()
+                .map(|token_account| token_account.mint),
+        )
+        .collect();
+
+    // A mint is an NFT while it has a single token without decimals, or when its metadata says
+    // so, which also covers burnt NFTs.
+    let mut mints: HashSet<Vec<u8>> = tokens::Entity::find()
+        .filter(tokens::Column::Mint.is_in(candidates.clone()))
+        .filter(tokens::Column::Decimals.eq(0))
+        .filter(tokens::Column::Supply.eq(1))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|token| token.mint)
+        .collect();
+    mints.extend(
+        asset::Entity::find()
+            .filter(asset::Column::Id.is_in(candidates))
+            .filter(
+                Condition::any()
+                    .add(asset::Column::SpecificationAssetClass.eq(SpecificationAssetClass::Nft))
+                    .add(
+                        asset::Column::SpecificationAssetClass
+                            .eq(SpecificationAssetClass::ProgrammableNft),
+                    ),
+            )
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|asset| asset.id),
+    );
+
+    if mints.is_empty() {
+        return Ok(());
+    }
+
+    let tx = bs58::decode(signature).into_vec().map_err(|_| {
+        IngesterError::DeserializationError(format!("Invalid signature {}", signature))
+    })?;
+    let items = mints.into_iter().map(|mint| asset_signatures::ActiveModel {
+        asset_id: Set(mint),
+        tx: Set(tx.clone()),
+        slot: Set(slot as i64),
+        ..Default::default()
+    });
+    let query = asset_signatures::Entity::insert_many(items)
+        .on_conflict(
+            OnConflict::columns([
+                asset_signatures::Column::AssetId,
+                asset_signatures::Column::Tx,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    conn.execute(query).await?;
+
+    Ok(())
+}
@@ -11,7 +11,9 @@ use blockbuster::token_metadata::{
 };
 use cadence_macros::{is_global_default_set, statsd_count};
 use chrono::Utc;
-use digital_asset_types::dao::{asset_authority, asset_data, asset_grouping, token_accounts};
+use digital_asset_types::dao::{
+    asset_authority, asset_data, asset_grouping, notify_asset_updated, token_accounts,
+};
 use digital_asset_types::{
     dao::{
         asset, asset_creators, asset_v1_account_attachments,
@@ -58,7 +60,9 @@ pub async fn burn_v1_asset<T: ConnectionTrait + TransactionTrait>(
         "{} WHERE excluded.slot_updated > asset.slot_updated",
         query.sql
     );
-    conn.execute(query).await?;
+    if conn.execute(query).await?.rows_affected() > 0 {
+        notify_asset_updated(conn, &id).await?;
+    }
     Ok(())
 }
 
@@ -134,6 +138,7 @@ async fn index_token_account_data<T: ConnectionTrait + TransactionTrait>(
                 slot_updated_token_account: Some(token_account.slot_updated),
             },
             conn,
+            true,
         )
         .await
         .map_err(|db_err| IngesterError::AssetIndexError(db_err.to_string()))?;
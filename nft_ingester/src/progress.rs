@@ -0,0 +1,251 @@
+use crate::metric;
+use cadence_macros::{is_global_default_set, statsd_count};
+use digital_asset_types::dao::{
+    ingester_failed_messages, ingester_progress, STALE_PROGRESS_SECONDS,
+};
+use log::error;
+use plerkle_messenger::{redis_messenger::DEFAULT_RETRIES, ConsumptionType, MessengerConfig};
+use sea_orm::{
+    query::*,
+    sea_query::{Expr, OnConflict},
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, NotSet, Set,
+};
+
+/// A message read from a stream, once handled.
+pub struct HandledMessage {
+    pub id: String,
+    pub slot: u64,
+    /// How many times the message was delivered before.
+    pub tries: usize,
+    pub acked: bool,
+}
+
+/// Tracks how far one consumer of a stream has processed it.
+///
+/// More messages for the highest slot of a batch may still be on their way, so after a batch the
+/// consumer is only through the slot before that, and while handling a batch it is at most
+/// through the slot before the lowest one in it. Messages that fail are tracked until a
+/// redelivery acks them or they run out of retries, holding the context slot below theirs.
+/// Messages still waiting in the stream are taken to be for slots at or after the ones already
+/// read from it.
+pub struct ConsumerProgress {
+    stream: &'static str,
+    consumer: String,
+    /// Consumers redelivering pending messages read from anywhere in the stream, so they have
+    /// no position of their own.
+    tracks_position: bool,
+    retries: usize,
+    slot: Option<u64>,
+}
+
+impl ConsumerProgress {
+    pub fn new(
+        stream: &'static str,
+        config: &MessengerConfig,
+        consumption_type: &ConsumptionType,
+    ) -> Self {
+        Self {
+            stream,
+            // The same defaults as the messenger's.
+            consumer: config
+                .get("consumer_id")
+                .and_then(|id| id.clone().into_string())
+                .unwrap_or_else(|| String::from("ingester")),
+            tracks_position: *consumption_type == ConsumptionType::New,
+            retries: config
+                .get("retries")
+                .and_then(|r| r.clone().to_u128().map(|n| n as usize))
+                .unwrap_or(DEFAULT_RETRIES),
+            slot: None,
+        }
+    }
+
+    /// Moves the consumer back before the slots of the batch it is about to handle, if needed.
+    pub async fn start_batch(
+        &mut self,
+        conn: &DatabaseConnection,
+        slots: impl IntoIterator<Item = u64>,
+    ) {
+        if !self.tracks_position {
+            return;
+        }
+        if let Some(lowest) = slots.into_iter().min() {
+            let slot = lowest.saturating_sub(1);
+            if self.slot.map_or(true, |current| slot < current) {
+                self.record(conn, slot).await;
+            }
+        }
+    }
+
+    pub async fn finish_batch(&mut self, conn: &DatabaseConnection, messages: &[HandledMessage]) {
+        let failed = messages
+            .iter()
+            .filter(|m| !m.acked && m.tries < self.retries)
+            .map(|m| (m.id.clone(), m.slot))
+            .collect();
+        // The messenger drops messages that failed on their last try.
+        let resolved = messages
+            .iter()
+            .filter(|m| (m.acked && m.tries > 0) || (!m.acked && m.tries >= self.retries))
+            .map(|m| m.id.clone())
+            .collect();
+        let result = async {
+            record_failed_messages(conn, self.stream, failed).await?;
+            clear_failed_messages(conn, self.stream, resolved).await
+        }
+        .await;
+        if let Err(err) = result {
+            self.report_error(err);
+        }
+
+        if self.tracks_position {
+            if let Some(highest) = messages.iter().map(|m| m.slot).max() {
+                self.record(conn, highest.saturating_sub(1)).await;
+            }
+        }
+    }
+
+    async fn record(&mut self, conn: &DatabaseConnection, slot: u64) {
+        match record_stream_progress(conn, self.stream, &self.consumer, slot).await {
+            Ok(()) => self.slot = Some(slot),
+            Err(err) => self.report_error(err),
+        }
+    }
+
+    fn report_error(&self, err: DbErr) {
+        error!("Error recording {} stream progress: {}", self.stream, err);
+        metric! {
+            statsd_count!("ingester.stream.progress_error", 1, "stream" => self.stream);
+        }
+    }
+}
+
+/// Records that `consumer` has processed `stream` through `slot`.
+pub async fn record_stream_progress(
+    conn: &DatabaseConnection,
+    stream: &str,
+    consumer: &str,
+    slot: u64,
+) -> Result<(), DbErr> {
+    // The update time is left to the database clock, which the API measures lag against.
+    let model = ingester_progress::ActiveModel {
+        stream: Set(stream.to_string()),
+        consumer: Set(consumer.to_string()),
+        slot: Set(slot as i64),
+        updated_at: NotSet,
+        backlog: NotSet,
+    };
+    ingester_progress::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([
+                ingester_progress::Column::Stream,
+                ingester_progress::Column::Consumer,
+            ])
+            .update_columns([
+                ingester_progress::Column::Slot,
+                ingester_progress::Column::UpdatedAt,
+            ])
+            .to_owned(),
+        )
+        .exec(conn)
+        .await
+        .map(|_| ())
+}
+
+/// Records messages of `stream` that failed and wait to be redelivered.
+pub async fn record_failed_messages(
+    conn: &DatabaseConnection,
+    stream: &str,
+    messages: Vec<(String, u64)>,
+) -> Result<(), DbErr> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+    let models = messages
+        .into_iter()
+        .map(|(id, slot)| ingester_failed_messages::ActiveModel {
+            stream: Set(stream.to_string()),
+            message_id: Set(id),
+            slot: Set(slot as i64),
+            updated_at: NotSet,
+        });
+    ingester_failed_messages::Entity::insert_many(models)
+        .on_conflict(
+            OnConflict::columns([
+                ingester_failed_messages::Column::Stream,
+                ingester_failed_messages::Column::MessageId,
+            ])
+            .update_columns([ingester_failed_messages::Column::UpdatedAt])
+            .to_owned(),
+        )
+        .exec(conn)
+        .await
+        .map(|_| ())
+}
+
+/// Forgets failed messages of `stream` that were acked after all or dropped.
+pub async fn clear_failed_messages(
+    conn: &DatabaseConnection,
+    stream: &str,
+    ids: Vec<String>,
+) -> Result<(), DbErr> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    ingester_failed_messages::Entity::delete_many()
+        .filter(ingester_failed_messages::Column::Stream.eq(stream))
+        .filter(ingester_failed_messages::Column::MessageId.is_in(ids))
+        .exec(conn)
+        .await
+        .map(|_| ())
+}
+
+/// Records the number of messages waiting in `stream`. Streams are only tracked once they have
+/// made progress, so this is a no-op before then.
+pub async fn record_stream_backlog(
+    conn: &DatabaseConnection,
+    stream: &str,
+    backlog: u64,
+) -> Result<(), DbErr> {
+    ingester_progress::Entity::update_many()
+        .col_expr(
+            ingester_progress::Column::Backlog,
+            Expr::value(backlog as i64),
+        )
+        .filter(ingester_progress::Column::Stream.eq(stream))
+        .exec(conn)
+        .await
+        .map(|_| ())
+}
+
+/// Deletes the progress of consumers of `stream` that went away, such as those of restarted
+/// ingesters, while others keep making progress, along with failed messages nobody retries.
+pub async fn expire_stream_progress(conn: &DatabaseConnection, stream: &str) -> Result<(), DbErr> {
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!(
+            "DELETE FROM ingester_progress stale
+            WHERE stream = $1
+                AND updated_at < LOCALTIMESTAMP - INTERVAL '{0} seconds'
+                AND EXISTS (
+                    SELECT 1 FROM ingester_progress fresh
+                    WHERE fresh.stream = stale.stream
+                        AND fresh.updated_at >= LOCALTIMESTAMP - INTERVAL '{0} seconds'
+                )",
+            STALE_PROGRESS_SECONDS
+        ),
+        [stream.into()],
+    ))
+    .await?;
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!(
+            "DELETE FROM ingester_failed_messages
+            WHERE stream = $1 AND updated_at < LOCALTIMESTAMP - INTERVAL '{} seconds'",
+            STALE_PROGRESS_SECONDS
+        ),
+        [stream.into()],
+    ))
+    .await?;
+    Ok(())
+}
@@ -1,8 +1,12 @@
-use crate::{error::IngesterError, metric};
+use crate::{
+    error::IngesterError,
+    progress::{expire_stream_progress, record_stream_backlog},
+};
 use cadence_macros::{is_global_default_set, statsd_count, statsd_gauge};
 
 use log::error;
 use plerkle_messenger::{Messenger, MessengerConfig};
+use sea_orm::DatabaseConnection;
 use tokio::{
     task::JoinHandle,
     time::{self, Duration},
@@ -27,33 +31,43 @@ impl StreamSizeTimer {
         })
     }
 
-    pub async fn start<T: Messenger>(&mut self) -> Option<JoinHandle<()>> {
-        metric! {
-            let i = self.interval;
-            let messenger_config = self.messenger_config.clone();
-            let stream = self.stream;
+    /// Periodically records the size of the stream as its backlog in the database, for the API's
+    /// indexer status, and expires the stream's stale progress. The size is also reported as a
+    /// metric when metrics are set up.
+    pub async fn start<T: Messenger>(&mut self, conn: DatabaseConnection) -> JoinHandle<()> {
+        let i = self.interval;
+        let messenger_config = self.messenger_config.clone();
+        let stream = self.stream;
+        let report_metrics = is_global_default_set();
 
-           return Some(tokio::spawn(async move {
+        tokio::spawn(async move {
             let messenger = T::new(messenger_config).await;
             if let Ok(mut messenger) = messenger {
-            let mut interval = time::interval(i);
+                let mut interval = time::interval(i);
                 loop {
                     interval.tick().await;
                     let size = messenger.stream_size(stream).await;
                     match size {
                         Ok(size) => {
-                            statsd_gauge!("ingester.stream_size", size, "stream" => stream);
+                            if report_metrics {
+                                statsd_gauge!("ingester.stream_size", size, "stream" => stream);
+                            }
+                            if let Err(e) = record_stream_backlog(&conn, stream, size).await {
+                                error!("Error recording stream backlog: {}", e);
+                            }
                         }
                         Err(e) => {
-                            statsd_count!("ingester.stream_size_error", 1, "stream" => stream);
+                            if report_metrics {
+                                statsd_count!("ingester.stream_size_error", 1, "stream" => stream);
+                            }
                             error!("Error getting stream size: {}", e);
                         }
                     }
+                    if let Err(e) = expire_stream_progress(&conn, stream).await {
+                        error!("Error expiring stream progress: {}", e);
+                    }
                 }
-            };
-            }));
-        }
-
-        None
+            }
+        })
     }
 }
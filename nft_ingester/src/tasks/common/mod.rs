@@ -1,7 +1,7 @@
 use super::{BgTask, FromTaskData, IngesterError, IntoTaskData, TaskData};
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use digital_asset_types::dao::asset_data;
+use digital_asset_types::dao::{asset_attributes, asset_data, notify_asset_updated};
 use log::debug;
 use reqwest::{Client, ClientBuilder};
 use sea_orm::*;
@@ -75,6 +75,56 @@ impl DownloadMetadataTask {
     }
 }
 
+/// Normalizes the `attributes` of off-chain metadata into rows of `asset_attributes`. Entries
+/// without a `trait_type` or with a non-scalar `value` are skipped.
+fn attributes_from_metadata(
+    asset_data_id: &[u8],
+    metadata: &serde_json::Value,
+) -> Vec<asset_attributes::ActiveModel> {
+    let Some(attributes) = metadata.get("attributes").and_then(|a| a.as_array()) else {
+        return Vec::new();
+    };
+
+    attributes
+        .iter()
+        .filter_map(|attribute| {
+            let trait_type = attribute.get("trait_type")?.as_str()?;
+            let value = match attribute.get("value")? {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::Bool(b) => b.to_string(),
+                _ => return None,
+            };
+            Some(asset_attributes::ActiveModel {
+                asset_data_id: Set(asset_data_id.to_vec()),
+                trait_type: Set(trait_type.replace('\0', "")),
+                value: Set(value.replace('\0', "")),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Replaces the attributes stored for `asset_data_id` with the ones in `metadata`.
+pub async fn save_asset_attributes<T: ConnectionTrait>(
+    conn: &T,
+    asset_data_id: &[u8],
+    metadata: &serde_json::Value,
+) -> Result<(), DbErr> {
+    asset_attributes::Entity::delete_many()
+        .filter(asset_attributes::Column::AssetDataId.eq(asset_data_id.to_vec()))
+        .exec(conn)
+        .await?;
+
+    let attributes = attributes_from_metadata(asset_data_id, metadata);
+    if !attributes.is_empty() {
+        asset_attributes::Entity::insert_many(attributes)
+            .exec(conn)
+            .await?;
+    }
+    Ok(())
+}
+
 #[derive(FromQueryResult, Debug, Default, Clone, Eq, PartialEq)]
 struct MetadataUrl {
     pub metadata_url: String,
@@ -135,6 +185,9 @@ impl BgTask for DownloadMetadataTask {
             }
         }
 
+        let txn = db.begin().await?;
+        save_asset_attributes(&txn, &download_metadata.asset_data_id, &body).await?;
+
         let model = asset_data::ActiveModel {
             id: Unchanged(download_metadata.asset_data_id.clone()),
             metadata: Set(body),
@@ -151,7 +204,7 @@ impl BgTask for DownloadMetadataTask {
                 Condition::all()
                     .add(asset_data::Column::MetadataUrl.eq(download_metadata.uri.clone())),
             )
-            .exec(db)
+            .exec(&txn)
             .await
             .map(|_| ())
             .map_err(|db| {
@@ -161,6 +214,9 @@ impl BgTask for DownloadMetadataTask {
                     db
                 ))
             })?;
+        notify_asset_updated(&txn, &download_metadata.asset_data_id).await?;
+
+        txn.commit().await?;
 
         if meta_url.is_err() {
             return Err(IngesterError::UnrecoverableTaskError(format!(
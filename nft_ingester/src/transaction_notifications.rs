@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
 use crate::{
-    metric, metrics::capture_result, program_transformers::ProgramTransformer, tasks::TaskData,
+    metric,
+    metrics::capture_result,
+    program_transformers::ProgramTransformer,
+    progress::{ConsumerProgress, HandledMessage},
+    tasks::TaskData,
 };
 use cadence_macros::{is_global_default_set, statsd_count, statsd_time};
 use chrono::Utc;
@@ -9,6 +13,7 @@ use log::{debug, error};
 use plerkle_messenger::{ConsumptionType, Messenger, MessengerConfig, RecvData};
 use plerkle_serialization::root_as_transaction_info;
 
+use sea_orm::SqlxPostgresConnector;
 use sqlx::{Pool, Postgres};
 use tokio::{
     sync::mpsc::UnboundedSender,
@@ -16,6 +21,7 @@ use tokio::{
     time::Instant,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn transaction_worker<T: Messenger>(
     pool: Pool<Postgres>,
     config: MessengerConfig,
@@ -23,18 +29,37 @@ pub fn transaction_worker<T: Messenger>(
     ack_channel: UnboundedSender<(&'static str, String)>,
     consumption_type: ConsumptionType,
     cl_audits: bool,
+    cl_items_history: bool,
+    asset_signatures: bool,
     stream_key: &'static str,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
+        let mut progress = ConsumerProgress::new(stream_key, &config, &consumption_type);
         let source = T::new(config).await;
         if let Ok(mut msg) = source {
-            let manager = Arc::new(ProgramTransformer::new(pool, bg_task_sender, cl_audits));
+            let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone());
+            let manager = Arc::new(ProgramTransformer::new(
+                pool,
+                bg_task_sender,
+                cl_audits,
+                cl_items_history,
+                asset_signatures,
+                false,
+            ));
             loop {
                 let e = msg.recv(stream_key, consumption_type.clone()).await;
                 let mut tasks = JoinSet::new();
                 match e {
                     Ok(data) => {
                         let len = data.len();
+                        progress
+                            .start_batch(
+                                &conn,
+                                data.iter()
+                                    .filter_map(|item| root_as_transaction_info(&item.data).ok())
+                                    .map(|tx| tx.slot()),
+                            )
+                            .await;
                         for item in data {
                             tasks.spawn(handle_transaction(Arc::clone(&manager), item, stream_key));
                         }
@@ -49,28 +74,34 @@ pub fn transaction_worker<T: Messenger>(
                         }
                     }
                 }
+                let mut handled = Vec::new();
                 while let Some(res) = tasks.join_next().await {
-                    if let Ok(Some(id)) = res {
-                        let send = ack_channel.send((stream_key, id));
-                        if let Err(err) = send {
-                            metric! {
-                                    error!("Txn stream ack error: {}", err);
-                                    statsd_count!("ingester.stream.ack_error", 1, "stream" => stream_key);
+                    if let Ok(Some(message)) = res {
+                        if message.acked {
+                            let send = ack_channel.send((stream_key, message.id.clone()));
+                            if let Err(err) = send {
+                                metric! {
+                                        error!("Txn stream ack error: {}", err);
+                                        statsd_count!("ingester.stream.ack_error", 1, "stream" => stream_key);
+                                }
                             }
                         }
+                        handled.push(message);
                     }
                 }
+                progress.finish_batch(&conn, &handled).await;
             }
         }
     })
 }
 
+/// Returns the message along with whether to acknowledge it, unless it could not be read.
 async fn handle_transaction(
     manager: Arc<ProgramTransformer>,
     item: RecvData,
     stream_key: &'static str,
-) -> Option<String> {
-    let mut ret_id = None;
+) -> Option<HandledMessage> {
+    let mut handled = None;
     if item.tries > 0 {
         metric! {
             statsd_count!("ingester.stream_redelivery", 1, "stream" => stream_key);
@@ -95,7 +126,7 @@ async fn handle_transaction(
 
         let begin = Instant::now();
         let res = manager.handle_transaction(&tx).await;
-        let should_ack = capture_result(
+        let acked = capture_result(
             id.clone(),
             stream_key,
             ("txn", "txn"),
@@ -105,9 +136,12 @@ async fn handle_transaction(
             tx.signature(),
             None,
         );
-        if should_ack {
-            ret_id = Some(id);
-        }
+        handled = Some(HandledMessage {
+            id,
+            slot: tx.slot(),
+            tries: item.tries,
+            acked,
+        });
     }
-    ret_id
+    handled
 }
@@ -0,0 +1,145 @@
+//! Maintenance of `cl_items_history`, the past versions of tree nodes that proofs at an earlier
+//! sequence number are rebuilt from: starting it from the nodes indexed before it was kept, and
+//! keeping it from growing without bound.
+
+use cadence_macros::{is_global_default_set, statsd_count};
+use log::{error, info};
+use sea_orm::{
+    ConnectionTrait, DbBackend, DbErr, FromQueryResult, SqlxPostgresConnector, Statement,
+};
+use sqlx::{Pool, Postgres};
+use tokio::time::{self, Duration};
+
+use crate::metric;
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(600);
+// Rows deleted per statement, so a prune never locks a large part of the table at once.
+const PRUNE_BATCH_SIZE: u64 = 10_000;
+// Range of `cl_items` ids copied per statement when backfilling the history.
+const BACKFILL_BATCH_SIZE: i64 = 10_000;
+
+#[derive(Debug, FromQueryResult)]
+struct TreeSeq {
+    tree: Vec<u8>,
+    seq: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct MaxId {
+    max_id: Option<i64>,
+}
+
+/// Starts the node history from the nodes indexed so far, for trees indexed before it was kept.
+pub fn setup_tree_history_backfill(pool: Pool<Postgres>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        match backfill_tree_history(&conn).await {
+            Ok(copied) => info!("Backfilled {} tree history items", copied),
+            Err(err) => error!("Failed to backfill tree history: {}", err),
+        }
+    })
+}
+
+/// Copies the current version of every node into the history, in batches of `cl_items` ids.
+/// Versions already in the history are left alone, so it can be run again if interrupted.
+pub async fn backfill_tree_history<T: ConnectionTrait>(conn: &T) -> Result<u64, DbErr> {
+    let max_id = MaxId::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT MAX(id) AS max_id FROM cl_items".to_string(),
+    ))
+    .one(conn)
+    .await?
+    .and_then(|row| row.max_id)
+    .unwrap_or_default();
+
+    let mut copied = 0;
+    for start in (0..=max_id).step_by(BACKFILL_BATCH_SIZE as usize) {
+        let result = conn
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "INSERT INTO cl_items_history (tree, node_idx, seq, level, hash)
+                SELECT tree, node_idx, seq, level, hash FROM cl_items
+                WHERE id >= $1 AND id < $2
+                ON CONFLICT DO NOTHING",
+                vec![start.into(), (start + BACKFILL_BATCH_SIZE).into()],
+            ))
+            .await?;
+        copied += result.rows_affected();
+    }
+    Ok(copied)
+}
+
+/// Periodically prunes the node history of every tree down to its last `retention` sequence
+/// numbers.
+pub fn setup_tree_history_pruning(
+    pool: Pool<Postgres>,
+    retention: u64,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        let mut interval = time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match prune_tree_history(&conn, retention).await {
+                Ok(pruned) => {
+                    info!("Pruned {} tree history items", pruned);
+                    metric! {
+                        statsd_count!("ingester.tree_history.pruned", pruned as i64);
+                    }
+                }
+                Err(err) => error!("Failed to prune tree history: {}", err),
+            }
+        }
+    })
+}
+
+/// Deletes the node versions no proof within `retention` sequence numbers of the latest change
+/// to a tree can need, those replaced at or before the oldest retained sequence number. Roots are
+/// all kept: they record which sequence numbers were indexed, and proofs rebuilt from pruned
+/// nodes don't lead to the root of their sequence number, so they are refused.
+pub async fn prune_tree_history<T: ConnectionTrait>(
+    conn: &T,
+    retention: u64,
+) -> Result<u64, DbErr> {
+    let trees = TreeSeq::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT tree, seq FROM cl_items WHERE node_idx = 1".to_string(),
+    ))
+    .all(conn)
+    .await?;
+
+    let mut pruned = 0;
+    for tree in trees {
+        let oldest_seq = tree.seq.saturating_sub_unsigned(retention);
+        if oldest_seq <= 0 {
+            continue;
+        }
+        loop {
+            let result = conn
+                .execute(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    "DELETE FROM cl_items_history WHERE id IN (
+                        SELECT h.id FROM cl_items_history h
+                        WHERE h.tree = $1 AND h.node_idx <> 1 AND h.seq < $2
+                        AND EXISTS (
+                            SELECT 1 FROM cl_items_history n
+                            WHERE n.tree = h.tree AND n.node_idx = h.node_idx
+                            AND n.seq > h.seq AND n.seq <= $2
+                        )
+                        LIMIT $3
+                    )",
+                    vec![
+                        tree.tree.clone().into(),
+                        oldest_seq.into(),
+                        (PRUNE_BATCH_SIZE as i64).into(),
+                    ],
+                ))
+                .await?;
+            pruned += result.rows_affected();
+            if result.rows_affected() < PRUNE_BATCH_SIZE {
+                break;
+            }
+        }
+    }
+    Ok(pruned)
+}